@@ -0,0 +1,89 @@
+//! System clipboard access for the `clipboard` feature.
+
+use std::error::Error;
+
+use raw_window_handle::RawDisplayHandle;
+
+/// Wraps the clipboard backend available on the current platform.
+pub(crate) enum Clipboard {
+    Arboard(arboard::Clipboard),
+    // arboard can only reach the Wayland clipboard through Xwayland, so a native
+    // Wayland clipboard needs the display of the event loop.
+    #[cfg(all(
+        unix,
+        not(any(target_os = "macos", target_os = "ios", target_os = "android"))
+    ))]
+    Wayland(smithay_clipboard::Clipboard),
+}
+
+impl Clipboard {
+    /// Creates a clipboard, using the given display on Wayland. Returns `None` if no clipboard
+    /// is available.
+    pub(crate) fn new(display: Option<RawDisplayHandle>) -> Option<Self> {
+        #[cfg(all(
+            unix,
+            not(any(target_os = "macos", target_os = "ios", target_os = "android"))
+        ))]
+        if let Some(RawDisplayHandle::Wayland(handle)) = display {
+            if !handle.display.is_null() {
+                // SAFETY: The display stays valid as long as the event loop it belongs to,
+                // which outlives the `Platform` as documented on `Platform::new_with_display`.
+                let clipboard = unsafe { smithay_clipboard::Clipboard::new(handle.display) };
+                return Some(Self::Wayland(clipboard));
+            }
+        }
+        #[cfg(not(all(
+            unix,
+            not(any(target_os = "macos", target_os = "ios", target_os = "android"))
+        )))]
+        let _ = display;
+
+        arboard::Clipboard::new().ok().map(Self::Arboard)
+    }
+
+    /// Reads the text content of the clipboard.
+    pub(crate) fn get_text(&mut self) -> Result<String, Box<dyn Error>> {
+        match self {
+            Self::Arboard(clipboard) => Ok(clipboard.get_text()?),
+            #[cfg(all(
+                unix,
+                not(any(target_os = "macos", target_os = "ios", target_os = "android"))
+            ))]
+            Self::Wayland(clipboard) => Ok(clipboard.load()?),
+        }
+    }
+
+    /// Replaces the content of the clipboard with the given text.
+    pub(crate) fn set_text(&mut self, text: String) -> Result<(), Box<dyn Error>> {
+        match self {
+            Self::Arboard(clipboard) => Ok(clipboard.set_text(text)?),
+            #[cfg(all(
+                unix,
+                not(any(target_os = "macos", target_os = "ios", target_os = "android"))
+            ))]
+            Self::Wayland(clipboard) => {
+                clipboard.store(text);
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Needs a desktop session and replaces the clipboard content, so it only runs on request.
+    #[test]
+    #[ignore]
+    fn text_round_trips_through_the_clipboard() {
+        let mut clipboard = Clipboard::new(None).expect("no clipboard available");
+        clipboard
+            .set_text("egui_winit_platform clipboard test".to_owned())
+            .unwrap();
+        assert_eq!(
+            clipboard.get_text().unwrap(),
+            "egui_winit_platform clipboard test"
+        );
+    }
+}
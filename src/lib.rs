@@ -5,22 +5,37 @@
 //! A basic usage example can be found [here](https://github.com/hasenbanck/egui_example).
 #![warn(missing_docs)]
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 #[cfg(feature = "clipboard")]
-use copypasta::{ClipboardContext, ClipboardProvider};
+mod clipboard;
+#[cfg(feature = "osc52")]
+mod osc52;
+#[cfg(feature = "software-render")]
+mod software_render;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod test_utils;
+
+#[cfg(feature = "accesskit")]
+pub use accesskit_winit;
+#[cfg(feature = "software-render")]
+pub use software_render::SoftwareRenderer;
+
+#[cfg(feature = "clipboard")]
+use clipboard::Clipboard;
 use egui::{
     emath::{pos2, vec2},
     Context, Key, Pos2,
 };
+use raw_window_handle::{HasRawDisplayHandle, RawDisplayHandle};
 use winit::{
-    dpi::PhysicalSize,
+    dpi::{PhysicalPosition, PhysicalSize},
     event::{Event, ModifiersState, TouchPhase, VirtualKeyCode, VirtualKeyCode::*, WindowEvent::*},
     window::CursorIcon,
 };
 
 /// Configures the creation of the `Platform`.
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct PlatformDescriptor {
     /// Width of the window in physical pixel.
     pub physical_width: u32,
@@ -32,40 +47,137 @@ pub struct PlatformDescriptor {
     pub font_definitions: egui::FontDefinitions,
     /// Egui style configuration.
     pub style: egui::Style,
+    /// Egui memory to start with, e.g. window positions saved by the last run. Saving and
+    /// loading it requires egui's `persistence` feature. Defaults to `None`.
+    pub memory: Option<egui::Memory>,
+    /// Points scrolled per line for mouse wheels that report line deltas. Defaults to 8.0.
+    pub scroll_line_height: f32,
+    /// Factor applied to all scroll deltas, for both line and pixel based scrolling.
+    /// Defaults to 1.0.
+    pub scroll_multiplier: f32,
+    /// Reverse the scroll direction, for both line and pixel based scrolling. winit already
+    /// reports deltas in the direction configured in the OS, so this is only needed to
+    /// deviate from it. Zooming is not affected. Defaults to `false`.
+    pub invert_scroll: bool,
+    /// Don't pass key presses generated by auto-repeat to egui. Text typed by holding a key
+    /// is still delivered. Defaults to `false`.
+    pub filter_key_repeats: bool,
+    /// Only let [`Platform::captures_event`] claim keyboard events while a text field has focus,
+    /// not for any focused widget. This keeps the application's shortcuts working while e.g.
+    /// a button is focused, at the cost of those keys reaching both egui and the application.
+    /// Defaults to `false`.
+    pub capture_keyboard_only_for_text: bool,
+    /// Turn the copy, cut and paste shortcuts into [`egui::Event::Copy`], [`egui::Event::Cut`]
+    /// and pasted text. If disabled, the keys only reach egui as ordinary key events, for
+    /// applications with their own clipboard handling. Defaults to `true`.
+    pub intercept_clipboard_shortcuts: bool,
+    /// Advance egui's time automatically on every [`Platform::begin_frame`], measured from the
+    /// creation of the `Platform`. Explicit calls to [`Platform::update_time`] still take
+    /// precedence. Has no effect on wasm32, where `std::time::Instant` is unavailable.
+    /// Defaults to `false`.
+    pub auto_time: bool,
+    /// Switch egui between its dark and light visuals whenever the system theme changes.
+    /// Defaults to `false`.
+    pub follow_system_theme: bool,
+    /// Whether the window has keyboard focus at creation, until the first `Focused` event
+    /// arrives. Defaults to `true`.
+    pub focused: bool,
+    /// Only handle events of the window with this id, for applications with several windows
+    /// that each have their own `Platform`. Defaults to `None`, which handles all windows.
+    pub window_id: Option<winit::window::WindowId>,
 }
 
-#[cfg(feature = "webbrowser")]
-fn handle_links(output: &egui::PlatformOutput) {
-    if let Some(open_url) = &output.open_url {
-        // This does not handle open_url.new_tab
-        // webbrowser does not support web anyway
-        if let Err(err) = webbrowser::open(&open_url.url) {
-            eprintln!("Failed to open url: {}", err);
+impl Default for PlatformDescriptor {
+    /// Describes a 800x600 window with a scale factor of 1.0, using egui's default fonts and style.
+    fn default() -> Self {
+        Self {
+            physical_width: 800,
+            physical_height: 600,
+            scale_factor: 1.0,
+            font_definitions: Default::default(),
+            style: Default::default(),
+            memory: None,
+            scroll_line_height: 8.0,
+            scroll_multiplier: 1.0,
+            invert_scroll: false,
+            filter_key_repeats: false,
+            capture_keyboard_only_for_text: false,
+            intercept_clipboard_shortcuts: true,
+            auto_time: false,
+            follow_system_theme: false,
+            focused: true,
+            window_id: None,
         }
     }
 }
 
+#[cfg(feature = "webbrowser")]
+fn handle_links(open_url: &egui::output::OpenUrl) {
+    // The target is only a hint. webbrowser only honors it on the web, desktop browsers
+    // decide on their own whether to open a new tab or window.
+    let target = if open_url.new_tab { "_blank" } else { "_self" };
+    if let Err(err) = webbrowser::open_browser_with_options(
+        webbrowser::Browser::Default,
+        &open_url.url,
+        webbrowser::BrowserOptions::new().with_target_hint(target),
+    ) {
+        eprintln!("Failed to open url: {}", err);
+    }
+}
+
 #[cfg(feature = "clipboard")]
-fn handle_clipboard(output: &egui::PlatformOutput, clipboard: Option<&mut ClipboardContext>) {
+fn handle_clipboard(output: &egui::PlatformOutput, clipboard: Option<&mut Clipboard>) {
     if !output.copied_text.is_empty() {
         if let Some(clipboard) = clipboard {
-            if let Err(err) = clipboard.set_contents(output.copied_text.clone()) {
+            if let Err(err) = clipboard.set_text(output.copied_text.clone()) {
                 eprintln!("Copy/Cut error: {}", err);
             }
         }
     }
 }
 
+type OpenUrlHandler = Box<dyn FnMut(&egui::output::OpenUrl)>;
+type CopiedTextHandler = Box<dyn FnMut(&str)>;
+type KeyTranslator = Box<dyn Fn(VirtualKeyCode) -> Option<egui::Key>>;
+
 /// Provides the integration between egui and winit.
+///
+/// `Platform` is not `Clone`: the clipboard connection and the installed handlers can't be
+/// duplicated, and cloning an egui [`Context`] only creates another handle to the same state,
+/// so a clone would not be an independent snapshot anyway. To snapshot a UI, create a new
+/// `Platform` and feed it the same events.
 pub struct Platform {
     scale_factor: f64,
+    window_id: Option<winit::window::WindowId>,
+    physical_size: PhysicalSize<u32>,
     context: Context,
     raw_input: egui::RawInput,
     modifier_state: ModifiersState,
     pointer_pos: Option<egui::Pos2>,
+    // Last cursor position in physical pixels, `None` until a `CursorMoved` inside the window.
+    physical_pointer_pos: Option<PhysicalPosition<f64>>,
+    scroll_line_height: f32,
+    scroll_multiplier: f32,
+    invert_scroll: bool,
+
+    // winit 0.28 has no repeat flag, so repeats are detected as presses of held keys.
+    pressed_keys: HashSet<VirtualKeyCode>,
+    filter_key_repeats: bool,
+    key_translator: Option<KeyTranslator>,
+
+    capture_keyboard_only_for_text: bool,
+    // Whether a text field had focus in the last frame.
+    text_input_active: bool,
+    text_cursor_pos: Option<Pos2>,
+    intercept_clipboard_shortcuts: bool,
 
     #[cfg(feature = "clipboard")]
-    clipboard: Option<ClipboardContext>,
+    clipboard: Option<Clipboard>,
+
+    open_url_handler: Option<OpenUrlHandler>,
+    copied_text_handler: Option<CopiedTextHandler>,
+    rotate_handler: Option<Box<dyn FnMut(f32)>>,
+    smart_magnify_handler: Option<Box<dyn FnMut()>>,
 
     // For emulating pointer events from touch events we merge multi-touch
     // pointers, and ref-count the press state.
@@ -75,43 +187,137 @@ pub struct Platform {
     // device IDs are opaque, so we have to create our own ID mapping.
     device_indices: HashMap<winit::event::DeviceId, u64>,
     next_device_index: u64,
+
+    // Windows reports Ime::Enabled/Disabled around every commit while macOS does not,
+    // so we track the start of a composition ourselves.
+    ime_composing: bool,
+    // The IME is disallowed by winit until `Window::set_ime_allowed` is called.
+    ime_allowed: bool,
+
+    repaint_after: std::time::Duration,
+    occluded: bool,
+    system_theme: Option<winit::window::Theme>,
+    follow_system_theme: bool,
+    // Set if `auto_time` is enabled.
+    #[cfg(not(target_arch = "wasm32"))]
+    start_time: Option<std::time::Instant>,
+
+    #[cfg(feature = "accesskit")]
+    accesskit: Option<accesskit_winit::Adapter>,
 }
 
 impl Platform {
     /// Creates a new `Platform`.
+    ///
+    /// On Wayland the clipboard can only be reached through Xwayland this way, use
+    /// [`Self::new_with_display`] to access the native Wayland clipboard.
     pub fn new(descriptor: PlatformDescriptor) -> Self {
+        Self::new_inner(descriptor, None, true)
+    }
+
+    /// Creates a new `Platform` that never sets up the system clipboard, even with the
+    /// `clipboard` feature enabled. Meant for tests, CI and headless renderers where
+    /// connecting to the clipboard can fail or has side effects. Clipboard shortcuts still
+    /// send [`egui::Event::Copy`] and [`egui::Event::Cut`], only the system clipboard is
+    /// not read or written.
+    pub fn new_without_clipboard(descriptor: PlatformDescriptor) -> Self {
+        Self::new_inner(descriptor, None, false)
+    }
+
+    /// Creates a new `Platform` for a window of the given physical size with a scale factor
+    /// of 1.0 and all other settings taken from [`PlatformDescriptor::default`].
+    pub fn new_default(physical_width: u32, physical_height: u32) -> Self {
+        Self::new(PlatformDescriptor {
+            physical_width,
+            physical_height,
+            ..Default::default()
+        })
+    }
+
+    /// Creates a new `Platform` that uses the display of the event loop (or a window) to set up
+    /// the clipboard. This is required to support the clipboard on Wayland without Xwayland.
+    /// The event loop that owns the display has to outlive the `Platform`.
+    pub fn new_with_display(
+        descriptor: PlatformDescriptor,
+        display: &impl HasRawDisplayHandle,
+    ) -> Self {
+        Self::new_inner(descriptor, Some(display.raw_display_handle()), true)
+    }
+
+    #[cfg_attr(not(feature = "clipboard"), allow(unused_variables))]
+    fn new_inner(
+        descriptor: PlatformDescriptor,
+        display: Option<RawDisplayHandle>,
+        with_clipboard: bool,
+    ) -> Self {
         let context = Context::default();
 
         context.set_fonts(descriptor.font_definitions.clone());
         context.set_style(descriptor.style);
+        if let Some(memory) = descriptor.memory {
+            context.memory_mut(|m| *m = memory);
+        }
         let raw_input = egui::RawInput {
             pixels_per_point: Some(descriptor.scale_factor as f32),
-            screen_rect: Some(egui::Rect::from_min_size(
-                Pos2::default(),
-                vec2(
-                    descriptor.physical_width as f32,
-                    descriptor.physical_height as f32,
-                ) / descriptor.scale_factor as f32,
-            )),
+            has_focus: descriptor.focused,
             ..Default::default()
         };
 
-        Self {
+        let mut platform = Self {
             scale_factor: descriptor.scale_factor,
+            window_id: descriptor.window_id,
+            physical_size: PhysicalSize::new(descriptor.physical_width, descriptor.physical_height),
             context,
             raw_input,
             modifier_state: winit::event::ModifiersState::empty(),
-            pointer_pos: Some(Pos2::default()),
+            pointer_pos: None,
+            physical_pointer_pos: None,
+            scroll_line_height: descriptor.scroll_line_height,
+            scroll_multiplier: descriptor.scroll_multiplier,
+            invert_scroll: descriptor.invert_scroll,
+            pressed_keys: HashSet::new(),
+            filter_key_repeats: descriptor.filter_key_repeats,
+            key_translator: None,
+            capture_keyboard_only_for_text: descriptor.capture_keyboard_only_for_text,
+            text_input_active: false,
+            text_cursor_pos: None,
+            intercept_clipboard_shortcuts: descriptor.intercept_clipboard_shortcuts,
             #[cfg(feature = "clipboard")]
-            clipboard: ClipboardContext::new().ok(),
+            clipboard: with_clipboard.then(|| Clipboard::new(display)).flatten(),
+            open_url_handler: None,
+            copied_text_handler: None,
+            rotate_handler: None,
+            smart_magnify_handler: None,
             touch_pointer_pressed: 0,
             device_indices: HashMap::new(),
             next_device_index: 1,
-        }
+            ime_composing: false,
+            ime_allowed: false,
+            repaint_after: std::time::Duration::ZERO,
+            occluded: false,
+            system_theme: None,
+            follow_system_theme: descriptor.follow_system_theme,
+            #[cfg(not(target_arch = "wasm32"))]
+            start_time: descriptor.auto_time.then(std::time::Instant::now),
+            #[cfg(feature = "accesskit")]
+            accesskit: None,
+        };
+        platform.update_screen_rect();
+        platform
     }
 
     /// Handles the given winit event and updates the egui context. Should be called before starting a new frame with `start_frame()`.
+    ///
+    /// `Event::UserEvent` is left to the application, as only it knows what its events mean.
+    /// Since user events can be sent from any thread through an `EventLoopProxy`, they are the
+    /// way to wake up the loop from background work: match on them in the event loop and call
+    /// `Window::request_redraw`, or push custom input through [`Self::raw_input_mut`].
     pub fn handle_event<T>(&mut self, winit_event: &Event<T>) {
+        if let Event::WindowEvent { window_id, .. } = winit_event {
+            if !self.is_own_window(*window_id) {
+                return;
+            }
+        }
         match winit_event {
             Event::WindowEvent {
                 window_id: _window_id,
@@ -126,52 +332,34 @@ impl Platform {
                     height: 0,
                 }) => {}
                 Resized(physical_size) => {
-                    self.raw_input.screen_rect = Some(egui::Rect::from_min_size(
-                        Default::default(),
-                        vec2(physical_size.width as f32, physical_size.height as f32)
-                            / self.scale_factor as f32,
-                    ));
+                    self.physical_size = *physical_size;
+                    self.update_screen_rect();
                 }
                 ScaleFactorChanged {
                     scale_factor,
                     new_inner_size,
                 } => {
                     self.scale_factor = *scale_factor;
+                    self.physical_size = **new_inner_size;
                     self.raw_input.pixels_per_point = Some(*scale_factor as f32);
-                    self.raw_input.screen_rect = Some(egui::Rect::from_min_size(
-                        Default::default(),
-                        vec2(new_inner_size.width as f32, new_inner_size.height as f32)
-                            / self.scale_factor as f32,
-                    ));
+                    self.update_screen_rect();
+                    self.update_pointer_pos();
                 }
                 MouseInput { state, button, .. } => {
-                    if let winit::event::MouseButton::Other(..) = button {
-                    } else {
-                        // push event only if the cursor is inside the window
-                        if let Some(pointer_pos) = self.pointer_pos {
-                            self.raw_input.events.push(egui::Event::PointerButton {
-                                pos: pointer_pos,
-                                button: match button {
-                                    winit::event::MouseButton::Left => egui::PointerButton::Primary,
-                                    winit::event::MouseButton::Right => {
-                                        egui::PointerButton::Secondary
-                                    }
-                                    winit::event::MouseButton::Middle => {
-                                        egui::PointerButton::Middle
-                                    }
-                                    winit::event::MouseButton::Other(_) => unreachable!(),
-                                },
-                                pressed: *state == winit::event::ElementState::Pressed,
-                                modifiers: Default::default(),
-                            });
-                        }
+                    // push event only if the cursor is inside the window
+                    if let (Some(pointer_pos), Some(button)) =
+                        (self.pointer_pos, winit_to_egui_mouse_button(*button))
+                    {
+                        self.raw_input.events.push(egui::Event::PointerButton {
+                            pos: pointer_pos,
+                            button,
+                            pressed: *state == winit::event::ElementState::Pressed,
+                            modifiers: winit_to_egui_modifiers(self.modifier_state),
+                        });
                     }
                 }
                 Touch(touch) => {
-                    let pointer_pos = pos2(
-                        touch.location.x as f32 / self.scale_factor as f32,
-                        touch.location.y as f32 / self.scale_factor as f32,
-                    );
+                    let pointer_pos = self.to_points(touch.location);
 
                     let device_id = match self.device_indices.get(&touch.device_id) {
                         Some(id) => *id,
@@ -190,8 +378,9 @@ impl Platform {
                     };
 
                     let force = match touch.force {
-                        Some(winit::event::Force::Calibrated { force, .. }) => force as f32,
-                        Some(winit::event::Force::Normalized(force)) => force as f32,
+                        // Calibrated forces are relative to the maximum force of the device,
+                        // egui expects a value between 0.0 and 1.0.
+                        Some(force) => (force.normalized() as f32).clamp(0.0, 1.0),
                         None => 0.0f32, // hmmm, egui can't differentiate unsupported from zero pressure
                     };
 
@@ -241,7 +430,7 @@ impl Platform {
                             pos: pointer_pos,
                             button: egui::PointerButton::Primary,
                             pressed: true,
-                            modifiers: Default::default(),
+                            modifiers: winit_to_egui_modifiers(self.modifier_state),
                         });
                     } else if was_pressed && self.touch_pointer_pressed == 0 {
                         // Egui docs say that the pressed=false should be sent _before_
@@ -250,7 +439,7 @@ impl Platform {
                             pos: pointer_pos,
                             button: egui::PointerButton::Primary,
                             pressed: false,
-                            modifiers: Default::default(),
+                            modifiers: winit_to_egui_modifiers(self.modifier_state),
                         });
                         self.raw_input.events.push(egui::Event::PointerGone);
                     }
@@ -258,41 +447,132 @@ impl Platform {
                 MouseWheel { delta, .. } => {
                     let mut delta = match delta {
                         winit::event::MouseScrollDelta::LineDelta(x, y) => {
-                            let line_height = 8.0; // TODO as in egui_glium
-                            vec2(*x, *y) * line_height
+                            vec2(*x, *y) * self.scroll_line_height
                         }
+                        // Pixel deltas are physical pixels, egui scrolls in points.
                         winit::event::MouseScrollDelta::PixelDelta(delta) => {
-                            vec2(delta.x as f32, delta.y as f32)
+                            vec2(delta.x as f32, delta.y as f32) / self.scale_factor as f32
                         }
                     };
                     if cfg!(target_os = "macos") {
                         // See https://github.com/rust-windowing/winit/issues/1695 for more info.
                         delta.x *= -1.0;
                     }
-
                     // The ctrl (cmd on macos) key indicates a zoom is desired.
                     if self.raw_input.modifiers.ctrl || self.raw_input.modifiers.command {
                         self.raw_input
                             .events
                             .push(egui::Event::Zoom((delta.y / 200.0).exp()));
                     } else {
-                        self.raw_input.events.push(egui::Event::Scroll(delta));
+                        // Shift turns a vertical wheel into horizontal scrolling on most platforms.
+                        // macOS already does this itself, which is why only y-only deltas are swapped.
+                        if self.modifier_state.shift() && delta.x == 0.0 {
+                            delta = vec2(delta.y, 0.0);
+                        }
+                        let direction = if self.invert_scroll { -1.0 } else { 1.0 };
+                        self.raw_input.events.push(egui::Event::Scroll(
+                            delta * self.scroll_multiplier * direction,
+                        ));
                     }
                 }
-                CursorMoved { position, .. } => {
-                    let pointer_pos = pos2(
-                        position.x as f32 / self.scale_factor as f32,
-                        position.y as f32 / self.scale_factor as f32,
-                    );
-                    self.pointer_pos = Some(pointer_pos);
+                // Only emitted by macOS, but the variant exists on every platform. The deltas of a
+                // gesture add up while egui multiplies zoom factors, hence the exponent.
+                TouchpadMagnify { delta, .. } => {
                     self.raw_input
                         .events
-                        .push(egui::Event::PointerMoved(pointer_pos));
+                        .push(egui::Event::Zoom((*delta as f32).exp()));
+                }
+                // egui has no rotation input, so the gesture is handed to the application.
+                TouchpadRotate { delta, .. } => {
+                    if let Some(handler) = &mut self.rotate_handler {
+                        handler(*delta);
+                    }
+                }
+                // Toggling between two zoom levels needs application state egui doesn't have.
+                SmartMagnify { .. } => {
+                    if let Some(handler) = &mut self.smart_magnify_handler {
+                        handler();
+                    }
+                }
+                CursorMoved { position, .. } => {
+                    let pointer_pos = self.to_points(*position);
+                    self.pointer_pos = Some(pointer_pos);
+                    self.physical_pointer_pos = Some(*position);
+                    // High polling rate mice send many moves per frame. A move directly following
+                    // another one replaces it, events in between (like clicks) keep their position.
+                    match self.raw_input.events.last_mut() {
+                        Some(egui::Event::PointerMoved(last_pos)) => *last_pos = pointer_pos,
+                        _ => self
+                            .raw_input
+                            .events
+                            .push(egui::Event::PointerMoved(pointer_pos)),
+                    }
                 }
+                // CursorEntered carries no position. winit sends a CursorMoved along with it,
+                // which makes the pointer present again.
                 CursorLeft { .. } => {
                     self.pointer_pos = None;
+                    // The exit position is stale by the time the cursor re-enters.
+                    self.physical_pointer_pos = None;
                     self.raw_input.events.push(egui::Event::PointerGone);
                 }
+                HoveredFile(path) => {
+                    self.raw_input.hovered_files.push(egui::HoveredFile {
+                        path: Some(path.clone()),
+                        ..Default::default()
+                    });
+                }
+                HoveredFileCancelled => {
+                    self.raw_input.hovered_files.clear();
+                }
+                DroppedFile(path) => {
+                    self.raw_input.hovered_files.clear();
+                    self.raw_input.dropped_files.push(egui::DroppedFile {
+                        path: Some(path.clone()),
+                        ..Default::default()
+                    });
+                }
+                Focused(focused) => {
+                    self.raw_input.has_focus = *focused;
+                    // Key releases are not reported while the window is unfocused,
+                    // so they would otherwise stay pressed after e.g. an Alt-Tab.
+                    if !*focused {
+                        // egui only releases keys on key events.
+                        for virtual_keycode in std::mem::take(&mut self.pressed_keys) {
+                            if let Some(key) = self.translate_key(virtual_keycode) {
+                                self.raw_input.events.push(egui::Event::Key {
+                                    key,
+                                    pressed: false,
+                                    modifiers: egui::Modifiers::default(),
+                                    repeat: false,
+                                });
+                            }
+                        }
+                        self.modifier_state = ModifiersState::empty();
+                        self.raw_input.modifiers = egui::Modifiers::default();
+                        self.release_pointer();
+                    } else if let Some(physical_pointer_pos) = self.physical_pointer_pos {
+                        // The cursor is still over the window if no CursorLeft arrived meanwhile,
+                        // so clicks without moving the mouse first must reach egui.
+                        let pointer_pos = self.to_points(physical_pointer_pos);
+                        self.pointer_pos = Some(pointer_pos);
+                        self.raw_input
+                            .events
+                            .push(egui::Event::PointerMoved(pointer_pos));
+                    }
+                }
+                Occluded(occluded) => {
+                    self.occluded = *occluded;
+                }
+                ThemeChanged(theme) => {
+                    self.system_theme = Some(*theme);
+                    if self.follow_system_theme {
+                        self.context.set_visuals(match theme {
+                            winit::window::Theme::Dark => egui::Visuals::dark(),
+                            winit::window::Theme::Light => egui::Visuals::light(),
+                        });
+                    }
+                }
                 ModifiersChanged(input) => {
                     self.modifier_state = *input;
                     self.raw_input.modifiers = winit_to_egui_modifiers(*input);
@@ -300,45 +580,63 @@ impl Platform {
                 KeyboardInput { input, .. } => {
                     if let Some(virtual_keycode) = input.virtual_keycode {
                         let pressed = input.state == winit::event::ElementState::Pressed;
-                        let ctrl = self.modifier_state.ctrl();
+                        let repeat = if pressed {
+                            !self.pressed_keys.insert(virtual_keycode)
+                        } else {
+                            self.pressed_keys.remove(&virtual_keycode);
+                            false
+                        };
+                        if repeat && self.filter_key_repeats {
+                            return;
+                        }
+                        // Clipboard shortcuts use cmd on macOS and ctrl everywhere else.
+                        #[cfg(target_os = "macos")]
+                        let command = self.modifier_state.logo();
+                        #[cfg(not(target_os = "macos"))]
+                        let command = self.modifier_state.ctrl();
 
-                        match (pressed, ctrl, virtual_keycode) {
-                            (true, true, VirtualKeyCode::C) => {
-                                self.raw_input.events.push(egui::Event::Copy)
-                            }
-                            (true, true, VirtualKeyCode::X) => {
-                                self.raw_input.events.push(egui::Event::Cut)
-                            }
-                            (true, true, VirtualKeyCode::V) => {
-                                #[cfg(feature = "clipboard")]
-                                if let Some(ref mut clipboard) = self.clipboard {
-                                    if let Ok(contents) = clipboard.get_contents() {
-                                        self.raw_input.events.push(egui::Event::Text(contents))
-                                    }
-                                }
-                            }
-                            _ => {
-                                if let Some(key) = winit_to_egui_key_code(virtual_keycode) {
-                                    self.raw_input.events.push(egui::Event::Key {
-                                        key,
-                                        pressed,
-                                        modifiers: winit_to_egui_modifiers(self.modifier_state),
-                                        repeat: false,
-                                    });
-                                }
-                            }
+                        // Clipboard shortcuts only trigger on the key press, the key events
+                        // themselves are always forwarded so egui sees balanced press/release pairs.
+                        let shortcut = pressed && command && self.intercept_clipboard_shortcuts;
+                        match (shortcut, virtual_keycode) {
+                            (true, VirtualKeyCode::C) => self.copy(),
+                            (true, VirtualKeyCode::X) => self.cut(),
+                            (true, VirtualKeyCode::V) => self.paste(),
+                            _ => {}
+                        }
+
+                        if let Some(key) = self.translate_key(virtual_keycode) {
+                            self.raw_input.events.push(egui::Event::Key {
+                                key,
+                                pressed,
+                                modifiers: winit_to_egui_modifiers(self.modifier_state),
+                                repeat: false,
+                            });
                         }
                     }
                 }
-                ReceivedCharacter(ch) => {
-                    if is_printable(*ch)
-                        && !self.modifier_state.ctrl()
-                        && !self.modifier_state.logo()
-                    {
+                Ime(ime) => match ime {
+                    winit::event::Ime::Enabled | winit::event::Ime::Disabled => {}
+                    winit::event::Ime::Preedit(text, _) => {
+                        if !self.ime_composing {
+                            self.ime_composing = true;
+                            self.raw_input.events.push(egui::Event::CompositionStart);
+                        }
+                        self.raw_input
+                            .events
+                            .push(egui::Event::CompositionUpdate(text.clone()));
+                    }
+                    winit::event::Ime::Commit(text) => {
+                        self.ime_composing = false;
                         self.raw_input
                             .events
-                            .push(egui::Event::Text(ch.to_string()));
+                            .push(egui::Event::CompositionEnd(text.clone()));
                     }
+                },
+                ReceivedCharacter(ch) if is_printable(*ch) && !self.shortcut_modifiers_held() => {
+                    self.raw_input
+                        .events
+                        .push(egui::Event::Text(ch.to_string()));
                 }
                 _ => {}
             },
@@ -347,19 +645,83 @@ impl Platform {
         }
     }
 
+    /// Handles a batch of winit events, e.g. recorded input that is replayed or events forwarded
+    /// from another thread. Equivalent to calling [`Self::handle_event`] for each event in order.
+    pub fn handle_events<T>(&mut self, winit_events: &[Event<T>]) {
+        for winit_event in winit_events {
+            self.handle_event(winit_event);
+        }
+    }
+
+    /// Returns `true` if events of the given window are meant for this `Platform`.
+    fn is_own_window(&self, window_id: winit::window::WindowId) -> bool {
+        self.window_id.is_none() || self.window_id == Some(window_id)
+    }
+
+    /// Translates the key with the installed key translator or the built-in mapping.
+    fn translate_key(&self, virtual_keycode: VirtualKeyCode) -> Option<Key> {
+        match &self.key_translator {
+            Some(translator) => translator(virtual_keycode),
+            None => winit_to_egui_key_code(virtual_keycode),
+        }
+    }
+
+    /// Returns `true` if the held modifiers turn key presses into shortcuts instead of text.
+    /// Windows reports AltGr as ctrl+alt, which still types characters like '@' on German layouts.
+    fn shortcut_modifiers_held(&self) -> bool {
+        self.modifier_state.logo() || (self.modifier_state.ctrl() && !self.modifier_state.alt())
+    }
+
+    /// Converts a physical position into egui points.
+    fn to_points(&self, position: PhysicalPosition<f64>) -> Pos2 {
+        pos2(
+            position.x as f32 / self.scale_factor as f32,
+            position.y as f32 / self.scale_factor as f32,
+        )
+    }
+
+    /// Derives the screen rect in points from the physical window size and the scale factor.
+    fn update_screen_rect(&mut self) {
+        self.raw_input.screen_rect = Some(self.screen_rect());
+    }
+
+    /// Recomputes the pointer position after the scale factor changed, so hover state stays
+    /// correct until the next `CursorMoved`.
+    fn update_pointer_pos(&mut self) {
+        if let (Some(_), Some(physical_pointer_pos)) = (self.pointer_pos, self.physical_pointer_pos)
+        {
+            let pointer_pos = self.to_points(physical_pointer_pos);
+            self.pointer_pos = Some(pointer_pos);
+            self.raw_input
+                .events
+                .push(egui::Event::PointerMoved(pointer_pos));
+        }
+    }
+
     /// Returns `true` if egui should handle the event exclusively. Check this to
     /// avoid unexpected interactions, e.g. a mouse click registering "behind" the UI.
     pub fn captures_event<T>(&self, winit_event: &Event<T>) -> bool {
+        if let Event::WindowEvent { window_id, .. } = winit_event {
+            if !self.is_own_window(*window_id) {
+                return false;
+            }
+        }
         match winit_event {
             Event::WindowEvent {
                 window_id: _window_id,
                 event,
             } => match event {
-                ReceivedCharacter(_) | KeyboardInput { .. } | ModifiersChanged(_) => {
-                    self.context().wants_keyboard_input()
+                ReceivedCharacter(_) | KeyboardInput { .. } | ModifiersChanged(_) | Ime(_) => {
+                    if self.capture_keyboard_only_for_text {
+                        self.text_input_active
+                    } else {
+                        self.context().wants_keyboard_input()
+                    }
                 }
 
-                MouseWheel { .. } | MouseInput { .. } => self.context().wants_pointer_input(),
+                MouseWheel { .. } | MouseInput { .. } | TouchpadMagnify { .. } => {
+                    self.context().wants_pointer_input()
+                }
 
                 CursorMoved { .. } => self.context().is_using_pointer(),
 
@@ -372,27 +734,150 @@ impl Platform {
         }
     }
 
+    /// Returns `true` if egui is interested in the pointer, e.g. because it hovers over an egui
+    /// window. This reflects the state after the last completed frame.
+    pub fn wants_pointer_input(&self) -> bool {
+        self.context.wants_pointer_input()
+    }
+
+    /// Returns `true` if egui wants keyboard input, e.g. because a text field has focus.
+    /// This reflects the state after the last completed frame.
+    pub fn wants_keyboard_input(&self) -> bool {
+        self.context.wants_keyboard_input()
+    }
+
     /// Updates the internal time for egui used for animations. `elapsed_seconds` should be the seconds since some point in time (for example application start).
     pub fn update_time(&mut self, elapsed_seconds: f64) {
         self.raw_input.time = Some(elapsed_seconds);
     }
 
+    /// Sets the predicted duration of the next frame in seconds, which egui uses for animations.
+    /// It stays in effect for all following frames until it is changed again.
+    pub fn set_predicted_dt(&mut self, predicted_dt: f32) {
+        self.raw_input.predicted_dt = predicted_dt;
+    }
+
+    /// Sets the largest texture size in pixels the renderer supports, e.g. from
+    /// `wgpu::Limits::max_texture_dimension_2d`, so egui doesn't allocate a larger font atlas.
+    /// It stays in effect for all following frames until it is changed again.
+    pub fn set_max_texture_side(&mut self, max_texture_side: usize) {
+        self.raw_input.max_texture_side = Some(max_texture_side);
+    }
+
     /// Starts a new frame by providing a new `Ui` instance to write into.
     pub fn begin_frame(&mut self) {
+        #[cfg(not(target_arch = "wasm32"))]
+        if let (None, Some(start_time)) = (self.raw_input.time, self.start_time) {
+            self.raw_input.time = Some(start_time.elapsed().as_secs_f64());
+        }
         self.context.begin_frame(self.raw_input.take());
     }
 
+    /// Starts a new frame with the given input instead of the input collected by
+    /// [`Self::handle_event`], e.g. for replaying recorded input. The collected input is kept
+    /// untouched and used by the next call to [`Self::begin_frame`].
+    pub fn begin_frame_with(&mut self, input: egui::RawInput) {
+        self.context.begin_frame(input);
+    }
+
     /// Ends the frame. Returns what has happened as `Output` and gives you the draw instructions
     /// as `PaintJobs`. If the optional `window` is set, it will set the cursor key based on
-    /// egui's instructions.
+    /// egui's instructions and only allow the IME while a text field has focus.
+    ///
+    /// Copied text and opened links are always handled, even without a `window`.
     pub fn end_frame(&mut self, window: Option<&winit::window::Window>) -> egui::FullOutput {
-        // otherwise the below line gets flagged by clippy if both clipboard and webbrowser features are disabled
-        #[allow(clippy::let_and_return)]
+        let output = self.end_frame_deferred();
+        self.apply_platform_output(window, &output.platform_output);
+        output
+    }
+
+    /// Ends the frame like [`Self::end_frame`], but doesn't apply any of the platform output.
+    /// Pass the output to [`Self::handle_platform_output`] later, e.g. once the window it
+    /// belongs to is known.
+    pub fn end_frame_deferred(&mut self) -> egui::FullOutput {
         let output = self.context.end_frame();
+        self.repaint_after = output.repaint_after;
+        output
+    }
+
+    /// Returns how long egui wants to wait before the next frame, as requested by the last
+    /// [`Self::end_frame`]. `Duration::ZERO` means as soon as possible, `Duration::MAX` means
+    /// egui only needs to repaint once new input arrives.
+    ///
+    /// This allows an event driven loop that only redraws when needed:
+    ///
+    /// ```no_run
+    /// # use std::time::Instant;
+    /// # use egui_winit_platform::{Platform, PlatformDescriptor};
+    /// # use winit::event::{Event, StartCause};
+    /// # use winit::event_loop::{ControlFlow, EventLoop};
+    /// # let event_loop = EventLoop::new();
+    /// # let window = winit::window::Window::new(&event_loop).unwrap();
+    /// # let mut platform = Platform::new(PlatformDescriptor::default());
+    /// event_loop.run(move |event, _, control_flow| {
+    ///     platform.handle_event(&event);
+    ///     match event {
+    ///         Event::NewEvents(StartCause::ResumeTimeReached { .. }) => window.request_redraw(),
+    ///         Event::WindowEvent { .. } => window.request_redraw(),
+    ///         Event::RedrawRequested(_) => {
+    ///             platform.begin_frame();
+    ///             // Draw the UI with `platform.context()` and render the output.
+    ///             let _output = platform.end_frame(Some(&window));
+    ///
+    ///             *control_flow = match Instant::now().checked_add(platform.repaint_after()) {
+    ///                 Some(deadline) => ControlFlow::WaitUntil(deadline),
+    ///                 None => ControlFlow::Wait,
+    ///             };
+    ///         }
+    ///         _ => {}
+    ///     }
+    /// });
+    /// ```
+    pub fn repaint_after(&self) -> std::time::Duration {
+        self.repaint_after
+    }
+
+    /// Returns `true` if the last [`Self::end_frame`] asked for another frame right away, e.g.
+    /// because a tooltip is fading in or a collapsing header is expanding. Animations only make
+    /// progress if the time advances between frames, see [`Self::update_time`].
+    pub fn is_animating(&self) -> bool {
+        self.repaint_after.is_zero()
+    }
+
+    /// Like [`Self::end_frame`], but also tessellates the shapes into meshes and returns the
+    /// resulting primitives. The `shapes` of the returned output are left empty.
+    pub fn end_frame_tessellated(
+        &mut self,
+        window: Option<&winit::window::Window>,
+    ) -> (egui::FullOutput, Vec<egui::ClippedPrimitive>) {
+        let mut output = self.end_frame(window);
+        let primitives = self.context.tessellate(std::mem::take(&mut output.shapes));
+        (output, primitives)
+    }
+
+    /// Applies the side effects egui requested in its output to the window and the system:
+    /// the cursor icon, the IME state and position, copied text and opened links.
+    /// This is the counterpart to [`Self::end_frame_deferred`]. Don't use it with the output of
+    /// [`Self::end_frame`], which already applied it, or text is copied and links are opened twice.
+    pub fn handle_platform_output(
+        &mut self,
+        window: &winit::window::Window,
+        output: &egui::PlatformOutput,
+    ) {
+        self.apply_platform_output(Some(window), output);
+    }
+
+    fn apply_platform_output(
+        &mut self,
+        window: Option<&winit::window::Window>,
+        output: &egui::PlatformOutput,
+    ) {
+        // egui only reports a text cursor while a text field has focus.
+        self.text_input_active = output.text_cursor_pos.is_some();
+        self.text_cursor_pos = output.text_cursor_pos;
 
         if let Some(window) = window {
-            if let Some(cursor_icon) = egui_to_winit_cursor_icon(output.platform_output.cursor_icon)
-            {
+            if let Some(cursor_icon) = egui_to_winit_cursor_icon(output.cursor_icon) {
                 window.set_cursor_visible(true);
                 // if the pointer is located inside the window, set cursor icon
                 if self.pointer_pos.is_some() {
@@ -401,15 +886,157 @@ impl Platform {
             } else {
                 window.set_cursor_visible(false);
             }
+
+            // Only show the IME over text fields, not for every widget with keyboard focus.
+            if self.text_input_active != self.ime_allowed {
+                self.ime_allowed = self.text_input_active;
+                window.set_ime_allowed(self.ime_allowed);
+            }
+            if let Some(pos) = output.text_cursor_pos {
+                window.set_ime_position(winit::dpi::PhysicalPosition::new(
+                    pos.x as f64 * self.scale_factor,
+                    pos.y as f64 * self.scale_factor,
+                ));
+            }
         }
 
-        #[cfg(feature = "clipboard")]
-        handle_clipboard(&output.platform_output, self.clipboard.as_mut());
+        if let Some(handler) = &mut self.copied_text_handler {
+            if !output.copied_text.is_empty() {
+                handler(&output.copied_text);
+            }
+        } else {
+            #[cfg(feature = "clipboard")]
+            handle_clipboard(output, self.clipboard.as_mut());
+            #[cfg(feature = "osc52")]
+            if !output.copied_text.is_empty() {
+                if let Err(err) = osc52::copy(&output.copied_text) {
+                    eprintln!("OSC 52 copy error: {}", err);
+                }
+            }
+        }
 
-        #[cfg(feature = "webbrowser")]
-        handle_links(&output.platform_output);
+        #[cfg(feature = "accesskit")]
+        if let (Some(accesskit), Some(update)) = (&self.accesskit, &output.accesskit_update) {
+            accesskit.update_if_active(|| update.clone());
+        }
 
-        output
+        if let Some(open_url) = &output.open_url {
+            if let Some(handler) = &mut self.open_url_handler {
+                handler(open_url);
+            } else {
+                #[cfg(feature = "webbrowser")]
+                handle_links(open_url);
+            }
+        }
+    }
+
+    /// Connects egui to the screen reader and other assistive technology through AccessKit.
+    ///
+    /// Action requests of assistive technology (e.g. clicking a button) are sent as user events
+    /// through the given event loop proxy and have to be passed back with
+    /// [`Self::on_accesskit_action_request`]. On Linux, window events also have to be passed to
+    /// [`Self::handle_accesskit_event`] so the window bounds are known.
+    ///
+    /// The adapter has to be created before the window is shown for the first time, so create
+    /// the window invisible and show it after calling this.
+    ///
+    /// ```no_run
+    /// # use egui_winit_platform::{Platform, PlatformDescriptor};
+    /// # use winit::event::Event;
+    /// # use egui_winit_platform::accesskit_winit::ActionRequestEvent;
+    /// # use winit::event_loop::EventLoopBuilder;
+    /// let event_loop = EventLoopBuilder::<ActionRequestEvent>::with_user_event().build();
+    /// let window = winit::window::WindowBuilder::new()
+    ///     .with_visible(false)
+    ///     .build(&event_loop)
+    ///     .unwrap();
+    /// let mut platform = Platform::new(PlatformDescriptor::default());
+    /// platform.init_accesskit(&window, event_loop.create_proxy());
+    /// window.set_visible(true);
+    ///
+    /// event_loop.run(move |event, _, _| {
+    ///     platform.handle_event(&event);
+    ///     match event {
+    ///         Event::WindowEvent { event, .. } => platform.handle_accesskit_event(&window, &event),
+    ///         Event::UserEvent(action) => platform.on_accesskit_action_request(action.request),
+    ///         _ => {}
+    ///     }
+    /// });
+    /// ```
+    #[cfg(feature = "accesskit")]
+    pub fn init_accesskit<T: From<accesskit_winit::ActionRequestEvent> + Send>(
+        &mut self,
+        window: &winit::window::Window,
+        event_loop_proxy: winit::event_loop::EventLoopProxy<T>,
+    ) {
+        let context = self.context.clone();
+        self.accesskit = Some(accesskit_winit::Adapter::new(
+            window,
+            move || {
+                // Called on the first request of an assistive technology. The real tree is only
+                // available after the next frame, so a placeholder is returned until then.
+                context.enable_accesskit();
+                context.request_repaint();
+                context.accesskit_placeholder_tree_update()
+            },
+            event_loop_proxy,
+        ));
+    }
+
+    /// Passes an action request of an assistive technology on to egui.
+    #[cfg(feature = "accesskit")]
+    pub fn on_accesskit_action_request(&mut self, request: egui::accesskit::ActionRequest) {
+        self.raw_input
+            .events
+            .push(egui::Event::AccessKitActionRequest(request));
+    }
+
+    /// Updates the AccessKit adapter with the window bounds from move and resize events.
+    /// Only needed on Linux, it does nothing on the other platforms.
+    #[cfg(feature = "accesskit")]
+    pub fn handle_accesskit_event(
+        &self,
+        window: &winit::window::Window,
+        event: &winit::event::WindowEvent,
+    ) {
+        if let Some(accesskit) = &self.accesskit {
+            let _ = accesskit.on_event(window, event);
+        }
+    }
+
+    /// Sets a handler that is called instead of opening links in the web browser
+    /// (with the `webbrowser` feature) whenever egui wants to open a url.
+    pub fn set_open_url_handler(&mut self, handler: impl FnMut(&egui::output::OpenUrl) + 'static) {
+        self.open_url_handler = Some(Box::new(handler));
+    }
+
+    /// Sets a handler that is called instead of writing to the system clipboard (with the
+    /// `clipboard` feature) or the terminal (with the `osc52` feature) whenever egui copies or
+    /// cuts text.
+    pub fn set_copied_text_handler(&mut self, handler: impl FnMut(&str) + 'static) {
+        self.copied_text_handler = Some(Box::new(handler));
+    }
+
+    /// Replaces the built-in translation of winit to egui keys, e.g. to remap keys or support
+    /// additional ones. Keys the translator returns `None` for are not passed to egui.
+    pub fn set_key_translator(
+        &mut self,
+        translator: impl Fn(VirtualKeyCode) -> Option<egui::Key> + 'static,
+    ) {
+        self.key_translator = Some(Box::new(translator));
+    }
+
+    /// Sets a handler that receives the rotation delta of touchpad rotation gestures, which egui
+    /// does not support itself. Positive values are counterclockwise. Only emitted on macOS.
+    pub fn set_rotate_handler(&mut self, handler: impl FnMut(f32) + 'static) {
+        self.rotate_handler = Some(Box::new(handler));
+    }
+
+    /// Sets a handler that is called for the smart magnification gesture (a two finger double
+    /// tap), which usually toggles between the default and a zoomed in view. Only emitted on
+    /// macOS.
+    pub fn set_smart_magnify_handler(&mut self, handler: impl FnMut() + 'static) {
+        self.smart_magnify_handler = Some(Box::new(handler));
     }
 
     /// Returns the internal egui context.
@@ -417,11 +1044,190 @@ impl Platform {
         self.context.clone()
     }
 
-    /// Returns a mutable reference to the raw input that will be passed to egui
+    /// Returns a copy of the egui memory, e.g. to save window positions and other UI state on
+    /// exit and restore it with [`PlatformDescriptor::memory`].
+    pub fn memory(&self) -> egui::Memory {
+        self.context.memory(|m| m.clone())
+    }
+
+    /// Returns `true` if the window is completely hidden, as reported by the last `Occluded` event.
+    /// Rendering can be skipped while this is the case.
+    pub fn is_occluded(&self) -> bool {
+        self.occluded
+    }
+
+    /// Returns the scale factor, i.e. the number of physical pixels per point, as set on creation
+    /// or by the last `ScaleFactorChanged` event or [`Self::set_pixels_per_point`].
+    pub fn scale_factor(&self) -> f64 {
+        self.scale_factor
+    }
+
+    /// Returns the screen area egui lays out the UI in, in points.
+    pub fn screen_rect(&self) -> egui::Rect {
+        egui::Rect::from_min_size(
+            Pos2::default(),
+            vec2(
+                self.physical_size.width as f32,
+                self.physical_size.height as f32,
+            ) / self.scale_factor as f32,
+        )
+    }
+
+    /// Returns the position of the text cursor in points, if a text field had focus in the last
+    /// frame. This is where the IME is placed, and can be used to position custom overlays.
+    pub fn text_cursor_pos(&self) -> Option<Pos2> {
+        self.text_cursor_pos
+    }
+
+    /// Returns the system theme from the last `ThemeChanged` event, or `None` if it hasn't
+    /// changed yet. Use `Window::theme` for the theme at startup.
+    pub fn system_theme(&self) -> Option<winit::window::Theme> {
+        self.system_theme
+    }
+
+    /// Returns the pointer position in points from the most recent `CursorMoved` event,
+    /// or `None` if the cursor is outside of the window or hasn't moved yet.
+    pub fn pointer_pos(&self) -> Option<egui::Pos2> {
+        self.pointer_pos
+    }
+
+    /// Overrides the pixels per point used by egui, e.g. for a UI scale setting independent of
+    /// the window's scale factor. The value is used for all further coordinate conversions
+    /// until it is replaced by the next `ScaleFactorChanged` event.
+    pub fn set_pixels_per_point(&mut self, pixels_per_point: f32) {
+        self.scale_factor = pixels_per_point as f64;
+        self.raw_input.pixels_per_point = Some(pixels_per_point);
+        self.update_screen_rect();
+        self.update_pointer_pos();
+    }
+
+    /// Takes the scale factor and size from the window, in case the window was created with
+    /// different values than the descriptor. Call it once after creating the window, winit
+    /// doesn't send `ScaleFactorChanged` for the initial scale factor on every platform.
+    pub fn sync_with_window(&mut self, window: &winit::window::Window) {
+        let size = window.inner_size();
+        if size.width > 0 && size.height > 0 {
+            self.physical_size = size;
+        }
+        self.scale_factor = window.scale_factor();
+        self.raw_input.pixels_per_point = Some(self.scale_factor as f32);
+        self.update_screen_rect();
+        self.update_pointer_pos();
+    }
+
+    /// Sets the points scrolled per line for mouse wheels that report line deltas.
+    pub fn set_scroll_line_height(&mut self, scroll_line_height: f32) {
+        self.scroll_line_height = scroll_line_height;
+    }
+
+    /// Restricts the `Platform` to the events of the given window, or lifts the restriction
+    /// with `None`.
+    pub fn set_window_id(&mut self, window_id: Option<winit::window::WindowId>) {
+        self.window_id = window_id;
+    }
+
+    /// Sets the factor applied to all scroll deltas.
+    pub fn set_scroll_multiplier(&mut self, scroll_multiplier: f32) {
+        self.scroll_multiplier = scroll_multiplier;
+    }
+
+    /// Sets whether the scroll direction is reversed.
+    pub fn set_invert_scroll(&mut self, invert_scroll: bool) {
+        self.invert_scroll = invert_scroll;
+    }
+
+    /// Replaces the egui font configuration. The new fonts are used starting with the next frame.
+    pub fn set_fonts(&self, font_definitions: egui::FontDefinitions) {
+        self.context.set_fonts(font_definitions);
+    }
+
+    /// Replaces the egui style configuration. The new style is used starting with the next frame.
+    pub fn set_style(&self, style: egui::Style) {
+        self.context.set_style(style);
+    }
+
+    /// Puts the given text into the system clipboard, e.g. for a "Copy link" button that is not
+    /// backed by egui's own copy handling.
+    #[cfg(feature = "clipboard")]
+    pub fn set_clipboard_text(&mut self, text: String) -> Result<(), Box<dyn std::error::Error>> {
+        match self.clipboard.as_mut() {
+            Some(clipboard) => clipboard.set_text(text),
+            None => Err("no clipboard available".into()),
+        }
+    }
+
+    /// Makes egui forget the pointer, dropping hover and press state and any scrolling that is
+    /// still queued. Useful when the application takes over input, e.g. for a modal outside of
+    /// egui or while the game is paused. The next `CursorMoved` event re-establishes the pointer,
+    /// until then clicks are not passed to egui.
+    pub fn release_pointer(&mut self) {
+        self.pointer_pos = None;
+        self.raw_input
+            .events
+            .retain(|event| !matches!(event, egui::Event::Scroll(_)));
+        self.raw_input.events.push(egui::Event::PointerGone);
+    }
+
+    /// Asks egui to copy the current selection, as if the copy shortcut was pressed. Useful for
+    /// menu entries or toolbar buttons.
+    pub fn copy(&mut self) {
+        self.raw_input.events.push(egui::Event::Copy);
+    }
+
+    /// Asks egui to cut the current selection, as if the cut shortcut was pressed.
+    pub fn cut(&mut self) {
+        self.raw_input.events.push(egui::Event::Cut);
+    }
+
+    /// Pastes the text content of the system clipboard, as if the paste shortcut was pressed.
+    ///
+    /// Does nothing without the `clipboard` feature or if the clipboard can't be read.
+    ///
+    /// The clipboard is read synchronously. On X11 and Wayland this waits for the application
+    /// owning the clipboard to send its content, which usually takes well under a millisecond
+    /// but can stall the frame if that application is busy. Use [`Self::paste_text`] with text
+    /// read on another thread to keep the event loop from blocking.
+    pub fn paste(&mut self) {
+        #[cfg(feature = "clipboard")]
+        if let Some(ref mut clipboard) = self.clipboard {
+            if let Ok(contents) = clipboard.get_text() {
+                self.paste_text(contents);
+            }
+        }
+    }
+
+    /// Pastes the given text into the focused widget without touching the system clipboard.
+    pub fn paste_text(&mut self, text: String) {
+        self.raw_input.events.push(egui::Event::Text(text));
+    }
+
+    /// Returns the egui events collected since the last call to [`Self::begin_frame`], which
+    /// clears them. Handy for debugging why a shortcut or click doesn't reach egui.
+    pub fn pending_events(&self) -> &[egui::Event] {
+        &self.raw_input.events
+    }
+
+    /// Returns a reference to the raw input that will be passed to egui
     /// the next time [`Self::begin_frame`] is called
+    pub fn raw_input(&self) -> &egui::RawInput {
+        &self.raw_input
+    }
+
+    /// Returns a mutable reference to the raw input that will be passed to egui
+    /// the next time [`Self::begin_frame`] is called. Changes only apply to that frame,
+    /// since [`Self::begin_frame`] takes the events and most optional fields out of it.
     pub fn raw_input_mut(&mut self) -> &mut egui::RawInput {
         &mut self.raw_input
     }
+
+    /// Discards all input queued since the last frame, e.g. after resuming a paused UI.
+    /// The screen size, pixels per point and time are kept. egui's own memory is not affected.
+    pub fn clear_input(&mut self) {
+        self.raw_input.events.clear();
+        self.raw_input.hovered_files.clear();
+        self.raw_input.dropped_files.clear();
+        self.ime_composing = false;
+    }
 }
 
 /// Translates winit to egui keycodes.
@@ -443,6 +1249,8 @@ fn winit_to_egui_key_code(key: VirtualKeyCode) -> Option<egui::Key> {
         Return => Key::Enter,
         Tab => Key::Tab,
         Space => Key::Space,
+        Minus => Key::Minus,
+        Equals | Plus => Key::PlusEquals,
         Key1 => Key::Num1,
         Key2 => Key::Num2,
         Key3 => Key::Num3,
@@ -453,6 +1261,19 @@ fn winit_to_egui_key_code(key: VirtualKeyCode) -> Option<egui::Key> {
         Key8 => Key::Num8,
         Key9 => Key::Num9,
         Key0 => Key::Num0,
+        Numpad1 => Key::Num1,
+        Numpad2 => Key::Num2,
+        Numpad3 => Key::Num3,
+        Numpad4 => Key::Num4,
+        Numpad5 => Key::Num5,
+        Numpad6 => Key::Num6,
+        Numpad7 => Key::Num7,
+        Numpad8 => Key::Num8,
+        Numpad9 => Key::Num9,
+        Numpad0 => Key::Num0,
+        NumpadEnter => Key::Enter,
+        NumpadAdd => Key::PlusEquals,
+        NumpadSubtract => Key::Minus,
         A => Key::A,
         B => Key::B,
         C => Key::C,
@@ -479,12 +1300,72 @@ fn winit_to_egui_key_code(key: VirtualKeyCode) -> Option<egui::Key> {
         X => Key::X,
         Y => Key::Y,
         Z => Key::Z,
+        F1 => Key::F1,
+        F2 => Key::F2,
+        F3 => Key::F3,
+        F4 => Key::F4,
+        F5 => Key::F5,
+        F6 => Key::F6,
+        F7 => Key::F7,
+        F8 => Key::F8,
+        F9 => Key::F9,
+        F10 => Key::F10,
+        F11 => Key::F11,
+        F12 => Key::F12,
+        F13 => Key::F13,
+        F14 => Key::F14,
+        F15 => Key::F15,
+        F16 => Key::F16,
+        F17 => Key::F17,
+        F18 => Key::F18,
+        F19 => Key::F19,
+        F20 => Key::F20,
         _ => {
             return None;
         }
     })
 }
 
+/// Codes of `MouseButton::Other` that winit reports for the back and forward side buttons.
+/// They differ per platform (and per windowing system on Linux).
+#[cfg(target_os = "windows")]
+const EXTRA_MOUSE_BUTTONS: &[(u16, egui::PointerButton)] = &[
+    (1, egui::PointerButton::Extra1),
+    (2, egui::PointerButton::Extra2),
+];
+#[cfg(target_os = "macos")]
+const EXTRA_MOUSE_BUTTONS: &[(u16, egui::PointerButton)] = &[
+    (3, egui::PointerButton::Extra1),
+    (4, egui::PointerButton::Extra2),
+];
+#[cfg(target_arch = "wasm32")]
+const EXTRA_MOUSE_BUTTONS: &[(u16, egui::PointerButton)] = &[
+    (0, egui::PointerButton::Extra1),
+    (1, egui::PointerButton::Extra2),
+];
+// X11 reports the raw button numbers, Wayland the evdev codes BTN_SIDE and BTN_EXTRA.
+#[cfg(not(any(target_os = "windows", target_os = "macos", target_arch = "wasm32")))]
+const EXTRA_MOUSE_BUTTONS: &[(u16, egui::PointerButton)] = &[
+    (8, egui::PointerButton::Extra1),
+    (9, egui::PointerButton::Extra2),
+    (0x113, egui::PointerButton::Extra1),
+    (0x114, egui::PointerButton::Extra2),
+];
+
+/// Translates winit to egui mouse buttons. Unknown `Other` buttons are ignored.
+#[inline]
+fn winit_to_egui_mouse_button(button: winit::event::MouseButton) -> Option<egui::PointerButton> {
+    match button {
+        winit::event::MouseButton::Left => Some(egui::PointerButton::Primary),
+        winit::event::MouseButton::Right => Some(egui::PointerButton::Secondary),
+        winit::event::MouseButton::Middle => Some(egui::PointerButton::Middle),
+        winit::event::MouseButton::Other(code) => EXTRA_MOUSE_BUTTONS
+            .iter()
+            .find(|(other, _)| *other == code)
+            .map(|(_, button)| *button),
+    }
+}
+
 /// Translates winit to egui modifier keys.
 #[inline]
 fn winit_to_egui_modifiers(modifiers: ModifiersState) -> egui::Modifiers {
@@ -503,8 +1384,10 @@ fn winit_to_egui_modifiers(modifiers: ModifiersState) -> egui::Modifiers {
     }
 }
 
+/// Translates egui to winit cursor icons. Returns `None` for [`egui::CursorIcon::None`],
+/// in which case the cursor should be hidden.
 #[inline]
-fn egui_to_winit_cursor_icon(icon: egui::CursorIcon) -> Option<winit::window::CursorIcon> {
+pub fn egui_to_winit_cursor_icon(icon: egui::CursorIcon) -> Option<winit::window::CursorIcon> {
     use egui::CursorIcon::*;
 
     match icon {
@@ -555,3 +1438,1099 @@ fn is_printable(chr: char) -> bool {
 
     !is_in_private_use_area && !chr.is_ascii_control()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::*;
+    use winit::event::{ElementState::*, MouseButton, WindowEvent};
+
+    fn platform() -> Platform {
+        Platform::new_without_clipboard(PlatformDescriptor::default())
+    }
+
+    fn platform_with(descriptor: PlatformDescriptor) -> Platform {
+        Platform::new_without_clipboard(descriptor)
+    }
+
+    // The modifier that triggers clipboard shortcuts and zooming.
+    fn command() -> ModifiersState {
+        if cfg!(target_os = "macos") {
+            ModifiersState::LOGO
+        } else {
+            ModifiersState::CTRL
+        }
+    }
+
+    fn key_events(platform: &Platform) -> Vec<(Key, bool)> {
+        platform
+            .pending_events()
+            .iter()
+            .filter_map(|event| match event {
+                egui::Event::Key { key, pressed, .. } => Some((*key, *pressed)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn scroll_events(platform: &Platform) -> Vec<egui::Vec2> {
+        platform
+            .pending_events()
+            .iter()
+            .filter_map(|event| match event {
+                egui::Event::Scroll(delta) => Some(*delta),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn key_press_and_release() {
+        let mut platform = platform();
+        platform.handle_events(&[
+            keyboard_input::<()>(Pressed, VirtualKeyCode::A),
+            keyboard_input(Released, VirtualKeyCode::A),
+        ]);
+        assert_eq!(key_events(&platform), [(Key::A, true), (Key::A, false)]);
+    }
+
+    #[test]
+    fn focus_loss_releases_held_keys() {
+        let mut platform = platform();
+        platform.handle_events(&[
+            keyboard_input::<()>(Pressed, VirtualKeyCode::W),
+            window_event(WindowEvent::Focused(false)),
+            window_event(WindowEvent::Focused(true)),
+        ]);
+        assert_eq!(key_events(&platform), [(Key::W, true), (Key::W, false)]);
+
+        platform.begin_frame();
+        platform.end_frame(None);
+        assert!(!platform.context().input(|i| i.key_down(Key::W)));
+    }
+
+    #[test]
+    fn focus_loss_clears_modifiers() {
+        let mut platform = platform();
+        platform.handle_events(&[
+            modifiers_changed::<()>(ModifiersState::CTRL | ModifiersState::SHIFT),
+            window_event(WindowEvent::Focused(false)),
+        ]);
+        assert_eq!(platform.raw_input().modifiers, egui::Modifiers::default());
+
+        // Without a ModifiersChanged after refocusing, ctrl must not turn typing into shortcuts.
+        platform.handle_events(&[
+            window_event::<()>(WindowEvent::Focused(true)),
+            received_character('a'),
+        ]);
+        assert!(platform
+            .pending_events()
+            .contains(&egui::Event::Text("a".to_owned())));
+    }
+
+    #[test]
+    fn release_pointer_queues_pointer_gone() {
+        let mut platform = platform();
+        platform.handle_events(&[cursor_moved::<()>(10.0, 10.0), mouse_wheel(0.0, 1.0)]);
+        platform.release_pointer();
+        assert_eq!(
+            platform.pending_events(),
+            [
+                egui::Event::PointerMoved(pos2(10.0, 10.0)),
+                egui::Event::PointerGone
+            ]
+        );
+        assert_eq!(platform.pointer_pos(), None);
+
+        // Clicks are dropped until the pointer is re-established.
+        platform.handle_event(&mouse_input::<()>(Pressed, MouseButton::Left));
+        assert_eq!(platform.pending_events().len(), 2);
+    }
+
+    #[test]
+    fn refocusing_restores_the_pointer() {
+        let mut platform = platform();
+        platform.handle_events(&[
+            cursor_moved::<()>(10.0, 10.0),
+            window_event(WindowEvent::Focused(false)),
+            window_event(WindowEvent::Focused(true)),
+            mouse_input(Pressed, MouseButton::Left),
+        ]);
+        assert_eq!(platform.pointer_pos(), Some(pos2(10.0, 10.0)));
+        assert!(matches!(
+            platform.pending_events(),
+            [
+                egui::Event::PointerMoved(_),
+                egui::Event::PointerGone,
+                egui::Event::PointerMoved(restored),
+                egui::Event::PointerButton { pos, pressed: true, .. },
+            ] if *restored == pos2(10.0, 10.0) && *pos == pos2(10.0, 10.0)
+        ));
+    }
+
+    #[test]
+    fn pointer_is_present_again_after_reentering() {
+        let mut platform = platform();
+        platform.handle_events(&[
+            cursor_moved::<()>(10.0, 10.0),
+            window_event(WindowEvent::CursorLeft {
+                device_id: device_id(),
+            }),
+            window_event(WindowEvent::CursorEntered {
+                device_id: device_id(),
+            }),
+        ]);
+        // The exit position is not re-used.
+        assert_eq!(platform.pointer_pos(), None);
+        assert_eq!(
+            platform.pending_events(),
+            [
+                egui::Event::PointerMoved(pos2(10.0, 10.0)),
+                egui::Event::PointerGone
+            ]
+        );
+
+        platform.handle_events(&[
+            cursor_moved::<()>(3.0, 4.0),
+            mouse_input(Pressed, MouseButton::Left),
+        ]);
+        assert_eq!(platform.pointer_pos(), Some(pos2(3.0, 4.0)));
+        assert!(matches!(
+            platform.pending_events(),
+            [
+                ..,
+                egui::Event::PointerMoved(_),
+                egui::Event::PointerButton { pressed: true, .. }
+            ]
+        ));
+    }
+
+    #[test]
+    fn clipboard_shortcuts_are_intercepted() {
+        let mut platform = platform();
+        platform.handle_events(&[
+            modifiers_changed::<()>(command()),
+            keyboard_input(Pressed, VirtualKeyCode::C),
+            keyboard_input(Released, VirtualKeyCode::C),
+            keyboard_input(Pressed, VirtualKeyCode::X),
+        ]);
+        assert!(matches!(
+            platform.pending_events(),
+            [
+                egui::Event::Copy,
+                egui::Event::Key {
+                    key: Key::C,
+                    pressed: true,
+                    ..
+                },
+                egui::Event::Key {
+                    key: Key::C,
+                    pressed: false,
+                    ..
+                },
+                egui::Event::Cut,
+                egui::Event::Key {
+                    key: Key::X,
+                    pressed: true,
+                    ..
+                },
+            ]
+        ));
+    }
+
+    #[test]
+    fn clipboard_shortcuts_pass_through_when_not_intercepted() {
+        let mut platform = platform_with(PlatformDescriptor {
+            intercept_clipboard_shortcuts: false,
+            ..Default::default()
+        });
+        platform.handle_events(&[
+            modifiers_changed::<()>(command()),
+            keyboard_input(Pressed, VirtualKeyCode::C),
+            keyboard_input(Pressed, VirtualKeyCode::X),
+        ]);
+        assert!(!platform
+            .pending_events()
+            .iter()
+            .any(|event| matches!(event, egui::Event::Copy | egui::Event::Cut)));
+        assert_eq!(key_events(&platform), [(Key::C, true), (Key::X, true)]);
+    }
+
+    #[test]
+    fn key_repeats_are_filtered() {
+        let events = [
+            keyboard_input::<()>(Pressed, VirtualKeyCode::A),
+            keyboard_input(Pressed, VirtualKeyCode::A),
+            keyboard_input(Released, VirtualKeyCode::A),
+        ];
+
+        let mut platform = platform();
+        platform.handle_events(&events);
+        assert_eq!(
+            key_events(&platform),
+            [(Key::A, true), (Key::A, true), (Key::A, false)]
+        );
+
+        let mut platform = platform_with(PlatformDescriptor {
+            filter_key_repeats: true,
+            ..Default::default()
+        });
+        platform.handle_events(&events);
+        assert_eq!(key_events(&platform), [(Key::A, true), (Key::A, false)]);
+    }
+
+    #[test]
+    fn consecutive_cursor_moves_are_coalesced() {
+        let mut platform = platform();
+        for i in 0..10 {
+            platform.handle_event(&cursor_moved::<()>(i as f64, 5.0));
+        }
+        assert_eq!(
+            platform.pending_events(),
+            [egui::Event::PointerMoved(pos2(9.0, 5.0))]
+        );
+
+        platform.handle_events(&[
+            mouse_input::<()>(Pressed, MouseButton::Left),
+            cursor_moved(20.0, 5.0),
+            cursor_moved(30.0, 5.0),
+        ]);
+        assert!(matches!(
+            platform.pending_events(),
+            [
+                egui::Event::PointerMoved(_),
+                egui::Event::PointerButton { pos, pressed: true, .. },
+                egui::Event::PointerMoved(last),
+            ] if *pos == pos2(9.0, 5.0) && *last == pos2(30.0, 5.0)
+        ));
+    }
+
+    #[test]
+    fn events_of_other_windows_are_ignored() {
+        let own = winit::window::WindowId::from(1);
+        let other = winit::window::WindowId::from(2);
+        let in_window = |window_id, event: Event<'static, ()>| match event {
+            Event::WindowEvent { event, .. } => Event::WindowEvent { window_id, event },
+            event => event,
+        };
+
+        let mut platform = platform_with(PlatformDescriptor {
+            window_id: Some(own),
+            ..Default::default()
+        });
+        platform.handle_events(&[
+            in_window(other, cursor_moved(1.0, 1.0)),
+            in_window(own, cursor_moved(2.0, 2.0)),
+            in_window(other, keyboard_input(Pressed, VirtualKeyCode::A)),
+        ]);
+        assert_eq!(
+            platform.pending_events(),
+            [egui::Event::PointerMoved(pos2(2.0, 2.0))]
+        );
+        assert!(!platform.captures_event(&in_window(other, mouse_wheel(0.0, 1.0))));
+
+        platform.set_window_id(None);
+        platform.handle_event(&in_window(
+            other,
+            keyboard_input(Pressed, VirtualKeyCode::A),
+        ));
+        assert_eq!(key_events(&platform), [(Key::A, true)]);
+    }
+
+    fn pointer_button_modifiers(
+        platform: &Platform,
+    ) -> Vec<(egui::PointerButton, egui::Modifiers)> {
+        platform
+            .pending_events()
+            .iter()
+            .filter_map(|event| match event {
+                egui::Event::PointerButton {
+                    button, modifiers, ..
+                } => Some((*button, *modifiers)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn ctrl_right_click_carries_ctrl() {
+        let mut platform = platform();
+        platform.handle_events(&[
+            cursor_moved::<()>(10.0, 10.0),
+            modifiers_changed(ModifiersState::CTRL),
+            mouse_input(Pressed, MouseButton::Right),
+            mouse_input(Released, MouseButton::Right),
+        ]);
+        let buttons = pointer_button_modifiers(&platform);
+        assert_eq!(buttons.len(), 2);
+        for (button, modifiers) in buttons {
+            assert_eq!(button, egui::PointerButton::Secondary);
+            assert!(modifiers.ctrl && !modifiers.shift && !modifiers.alt);
+        }
+    }
+
+    #[test]
+    fn shift_middle_click_carries_shift() {
+        let mut platform = platform();
+        platform.handle_events(&[
+            cursor_moved::<()>(10.0, 10.0),
+            modifiers_changed(ModifiersState::SHIFT),
+            mouse_input(Pressed, MouseButton::Middle),
+        ]);
+        assert_eq!(
+            pointer_button_modifiers(&platform),
+            [(egui::PointerButton::Middle, egui::Modifiers::SHIFT)]
+        );
+    }
+
+    #[test]
+    fn shift_scrolls_horizontally() {
+        let mut platform = platform();
+        platform.handle_events(&[
+            modifiers_changed::<()>(ModifiersState::SHIFT),
+            mouse_wheel(0.0, 1.0),
+        ]);
+        assert_eq!(scroll_events(&platform), [vec2(8.0, 0.0)]);
+    }
+
+    #[test]
+    fn shift_doesnt_cancel_zooming() {
+        let mut platform = platform();
+        platform.handle_events(&[
+            modifiers_changed::<()>(command() | ModifiersState::SHIFT),
+            mouse_wheel(0.0, 1.0),
+        ]);
+        assert!(matches!(
+            platform.pending_events(),
+            [egui::Event::Zoom(zoom)] if *zoom > 1.0
+        ));
+    }
+
+    #[test]
+    fn scroll_multiplier_scales_line_and_pixel_deltas() {
+        let events = [mouse_wheel::<()>(0.0, 1.0), mouse_wheel_pixels(3.0, 4.0)];
+
+        let mut platform = platform();
+        platform.handle_events(&events);
+        let normal = scroll_events(&platform);
+
+        let mut platform = platform_with(PlatformDescriptor {
+            scroll_multiplier: 2.0,
+            ..Default::default()
+        });
+        platform.handle_events(&events);
+        let doubled = scroll_events(&platform);
+
+        assert_eq!(doubled, normal.iter().map(|d| *d * 2.0).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn invert_scroll_flips_line_and_pixel_deltas() {
+        let events = [mouse_wheel::<()>(0.0, 1.0), mouse_wheel_pixels(3.0, 4.0)];
+
+        let mut platform = platform();
+        platform.handle_events(&events);
+        let normal = scroll_events(&platform);
+
+        let mut platform = platform_with(PlatformDescriptor {
+            invert_scroll: true,
+            ..Default::default()
+        });
+        platform.handle_events(&events);
+        let inverted = scroll_events(&platform);
+
+        assert_eq!(inverted, normal.iter().map(|d| -*d).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn pixel_scroll_deltas_are_converted_to_points() {
+        let mut platform = platform_with(PlatformDescriptor {
+            scale_factor: 2.0,
+            ..Default::default()
+        });
+        platform.handle_event(&mouse_wheel_pixels::<()>(0.0, 10.0));
+        assert_eq!(scroll_events(&platform), [vec2(0.0, 5.0)]);
+    }
+
+    #[test]
+    fn function_keys_are_translated() {
+        use VirtualKeyCode::*;
+        let winit_keys = [
+            F1, F2, F3, F4, F5, F6, F7, F8, F9, F10, F11, F12, F13, F14, F15, F16, F17, F18, F19,
+            F20,
+        ];
+        let egui_keys = [
+            Key::F1,
+            Key::F2,
+            Key::F3,
+            Key::F4,
+            Key::F5,
+            Key::F6,
+            Key::F7,
+            Key::F8,
+            Key::F9,
+            Key::F10,
+            Key::F11,
+            Key::F12,
+            Key::F13,
+            Key::F14,
+            Key::F15,
+            Key::F16,
+            Key::F17,
+            Key::F18,
+            Key::F19,
+            Key::F20,
+        ];
+        for (winit_key, egui_key) in winit_keys.iter().zip(&egui_keys) {
+            let mut platform = platform();
+            platform.handle_events(&[
+                keyboard_input::<()>(Pressed, *winit_key),
+                keyboard_input(Released, *winit_key),
+            ]);
+            assert_eq!(
+                key_events(&platform),
+                [(*egui_key, true), (*egui_key, false)],
+                "{:?}",
+                winit_key
+            );
+        }
+        assert_eq!(winit_to_egui_key_code(F21), None);
+    }
+
+    #[test]
+    fn numpad_keys_are_translated() {
+        let mut platform = platform();
+        platform.handle_events(&[
+            keyboard_input::<()>(Pressed, VirtualKeyCode::Numpad7),
+            keyboard_input(Pressed, VirtualKeyCode::NumpadEnter),
+            keyboard_input(Pressed, VirtualKeyCode::NumpadAdd),
+            keyboard_input(Pressed, VirtualKeyCode::NumpadSubtract),
+            // With NumLock off, winit already reports the navigation key.
+            keyboard_input(Pressed, VirtualKeyCode::Home),
+        ]);
+        assert_eq!(
+            key_events(&platform),
+            [
+                (Key::Num7, true),
+                (Key::Enter, true),
+                (Key::PlusEquals, true),
+                (Key::Minus, true),
+                (Key::Home, true)
+            ]
+        );
+    }
+
+    #[test]
+    fn ctrl_equals_is_a_zoom_in_shortcut() {
+        let mut platform = platform();
+        platform.handle_events(&[
+            modifiers_changed::<()>(command()),
+            keyboard_input(Pressed, VirtualKeyCode::Equals),
+            // Typing "+" with shift still reports the same key.
+            modifiers_changed(command() | ModifiersState::SHIFT),
+            keyboard_input(Pressed, VirtualKeyCode::Plus),
+            modifiers_changed(command()),
+            keyboard_input(Pressed, VirtualKeyCode::Minus),
+        ]);
+        let keys: Vec<_> = platform
+            .pending_events()
+            .iter()
+            .filter_map(|event| match event {
+                egui::Event::Key { key, modifiers, .. } => Some((*key, modifiers.command)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            keys,
+            [
+                (Key::PlusEquals, true),
+                (Key::PlusEquals, true),
+                (Key::Minus, true)
+            ]
+        );
+    }
+
+    #[test]
+    fn every_cursor_icon_but_none_is_translated() {
+        for icon in egui::CursorIcon::ALL {
+            let translated = egui_to_winit_cursor_icon(icon);
+            if icon == egui::CursorIcon::None {
+                assert_eq!(translated, None);
+            } else {
+                assert!(translated.is_some(), "{:?}", icon);
+            }
+        }
+        assert_eq!(
+            egui_to_winit_cursor_icon(egui::CursorIcon::PointingHand),
+            Some(CursorIcon::Hand)
+        );
+    }
+
+    fn text_events(platform: &Platform) -> Vec<String> {
+        platform
+            .pending_events()
+            .iter()
+            .filter_map(|event| match event {
+                egui::Event::Text(text) => Some(text.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn altgr_characters_are_typed() {
+        // AltGr+Q on a German layout, reported as ctrl+alt on Windows.
+        let mut platform = platform();
+        platform.handle_events(&[
+            modifiers_changed::<()>(ModifiersState::CTRL | ModifiersState::ALT),
+            received_character('@'),
+        ]);
+        assert_eq!(text_events(&platform), ["@"]);
+    }
+
+    #[test]
+    fn shortcuts_type_no_text() {
+        let mut platform = platform();
+        platform.handle_events(&[
+            modifiers_changed::<()>(ModifiersState::CTRL),
+            received_character('a'),
+            modifiers_changed(ModifiersState::LOGO),
+            received_character('a'),
+        ]);
+        assert!(text_events(&platform).is_empty());
+    }
+
+    #[test]
+    fn focus_flag_follows_the_last_focused_event() {
+        let mut platform = platform();
+        platform.handle_event(&window_event::<()>(WindowEvent::Focused(false)));
+        assert!(!platform.raw_input().has_focus);
+        platform.handle_event(&window_event::<()>(WindowEvent::Focused(true)));
+        assert!(platform.raw_input().has_focus);
+    }
+
+    #[test]
+    fn hovered_files_are_cancelled_or_dropped() {
+        use std::path::PathBuf;
+
+        let mut platform = platform();
+        platform.handle_events(&[
+            window_event::<()>(WindowEvent::HoveredFile(PathBuf::from("a.txt"))),
+            window_event(WindowEvent::HoveredFileCancelled),
+        ]);
+        assert!(platform.raw_input().hovered_files.is_empty());
+
+        platform.handle_event(&window_event::<()>(WindowEvent::HoveredFile(
+            PathBuf::from("b.txt"),
+        )));
+        assert_eq!(
+            platform.raw_input().hovered_files[0].path,
+            Some(PathBuf::from("b.txt"))
+        );
+        platform.handle_event(&window_event::<()>(WindowEvent::DroppedFile(
+            PathBuf::from("b.txt"),
+        )));
+        let raw_input = platform.raw_input();
+        assert!(raw_input.hovered_files.is_empty());
+        assert_eq!(raw_input.dropped_files.len(), 1);
+        assert_eq!(
+            raw_input.dropped_files[0].path,
+            Some(PathBuf::from("b.txt"))
+        );
+    }
+
+    #[test]
+    fn ime_preedit_and_commit_compose_text() {
+        use winit::event::Ime;
+
+        let mut platform = platform();
+        platform.handle_events(&[
+            window_event::<()>(WindowEvent::Ime(Ime::Enabled)),
+            window_event(WindowEvent::Ime(Ime::Preedit("k".to_owned(), Some((1, 1))))),
+            window_event(WindowEvent::Ime(Ime::Preedit(
+                "か".to_owned(),
+                Some((3, 3)),
+            ))),
+            window_event(WindowEvent::Ime(Ime::Commit("か".to_owned()))),
+        ]);
+        assert_eq!(
+            platform.pending_events(),
+            [
+                egui::Event::CompositionStart,
+                egui::Event::CompositionUpdate("k".to_owned()),
+                egui::Event::CompositionUpdate("か".to_owned()),
+                egui::Event::CompositionEnd("か".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn occlusion_flag_follows_occluded_events() {
+        let mut platform = platform();
+        assert!(!platform.is_occluded());
+        platform.handle_event(&window_event::<()>(WindowEvent::Occluded(true)));
+        assert!(platform.is_occluded());
+        platform.handle_event(&window_event::<()>(WindowEvent::Occluded(false)));
+        assert!(!platform.is_occluded());
+    }
+
+    #[test]
+    fn initial_focus_comes_from_the_descriptor() {
+        assert!(platform().raw_input().has_focus);
+        let platform = platform_with(PlatformDescriptor {
+            focused: false,
+            ..Default::default()
+        });
+        assert!(!platform.raw_input().has_focus);
+    }
+
+    #[test]
+    fn screen_rect_shrinks_with_more_pixels_per_point() {
+        let mut platform = platform_with(PlatformDescriptor {
+            physical_width: 800,
+            physical_height: 600,
+            ..Default::default()
+        });
+        assert_eq!(platform.screen_rect().size(), vec2(800.0, 600.0));
+        platform.set_pixels_per_point(2.0);
+        assert_eq!(platform.screen_rect().size(), vec2(400.0, 300.0));
+        assert_eq!(platform.raw_input().pixels_per_point, Some(2.0));
+
+        // Later cursor positions use the new factor as well.
+        platform.handle_event(&cursor_moved::<()>(100.0, 50.0));
+        assert_eq!(platform.pointer_pos(), Some(pos2(50.0, 25.0)));
+    }
+
+    #[test]
+    fn scroll_line_height_scales_line_deltas() {
+        let mut platform = platform_with(PlatformDescriptor {
+            scroll_line_height: 10.0,
+            ..Default::default()
+        });
+        platform.handle_event(&mouse_wheel::<()>(0.0, 1.0));
+        platform.set_scroll_line_height(30.0);
+        platform.handle_event(&mouse_wheel::<()>(0.0, 1.0));
+        assert_eq!(scroll_events(&platform), [vec2(0.0, 10.0), vec2(0.0, 30.0)]);
+    }
+
+    #[test]
+    fn scale_factor_change_moves_the_pointer() {
+        let mut platform = platform();
+        platform.handle_events(&[
+            cursor_moved::<()>(100.0, 50.0),
+            window_event(WindowEvent::ScaleFactorChanged {
+                scale_factor: 2.0,
+                // The event borrows the size mutably, so that it can be adjusted by the handler.
+                new_inner_size: Box::leak(Box::new(PhysicalSize::new(1600, 1200))),
+            }),
+        ]);
+        assert_eq!(platform.pointer_pos(), Some(pos2(50.0, 25.0)));
+        assert_eq!(
+            platform.pending_events(),
+            [
+                egui::Event::PointerMoved(pos2(100.0, 50.0)),
+                egui::Event::PointerMoved(pos2(50.0, 25.0))
+            ]
+        );
+    }
+
+    #[test]
+    fn resize_and_scale_changes_compose() {
+        let mut platform = platform();
+        platform.handle_event(&resized::<()>(1000, 500));
+        platform.set_pixels_per_point(2.0);
+        assert_eq!(
+            platform.screen_rect(),
+            egui::Rect::from_min_size(Pos2::ZERO, vec2(500.0, 250.0))
+        );
+        assert_eq!(
+            platform.raw_input().screen_rect,
+            Some(platform.screen_rect())
+        );
+
+        platform.handle_event(&resized::<()>(400, 200));
+        assert_eq!(platform.screen_rect().size(), vec2(200.0, 100.0));
+    }
+
+    #[test]
+    fn key_translator_replaces_the_built_in_mapping() {
+        let mut platform = platform();
+        platform.set_key_translator(|key| match key {
+            VirtualKeyCode::Capital => Some(Key::Escape),
+            _ => None,
+        });
+        platform.handle_events(&[
+            keyboard_input::<()>(Pressed, VirtualKeyCode::Capital),
+            keyboard_input(Pressed, VirtualKeyCode::A),
+            keyboard_input(Released, VirtualKeyCode::Capital),
+        ]);
+        assert_eq!(
+            key_events(&platform),
+            [(Key::Escape, true), (Key::Escape, false)]
+        );
+    }
+
+    #[test]
+    fn copied_text_handler_receives_copied_text() {
+        use std::{cell::RefCell, rc::Rc};
+
+        let copied = Rc::new(RefCell::new(Vec::new()));
+        let mut platform = platform();
+        platform.set_copied_text_handler({
+            let copied = copied.clone();
+            move |text| copied.borrow_mut().push(text.to_owned())
+        });
+
+        platform.begin_frame();
+        platform
+            .context()
+            .output_mut(|o| o.copied_text = "hello".to_owned());
+        platform.end_frame(None);
+        // Frames without copied text don't call the handler.
+        platform.begin_frame();
+        platform.end_frame(None);
+
+        assert_eq!(*copied.borrow(), ["hello"]);
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn auto_time_advances_between_frames() {
+        let mut platform = platform_with(PlatformDescriptor {
+            auto_time: true,
+            ..Default::default()
+        });
+        platform.begin_frame();
+        let first = platform.context().input(|i| i.time);
+        platform.end_frame(None);
+
+        std::thread::sleep(std::time::Duration::from_millis(2));
+        platform.begin_frame();
+        let second = platform.context().input(|i| i.time);
+        platform.end_frame(None);
+
+        assert!(second > first, "{} <= {}", second, first);
+    }
+
+    #[test]
+    fn memory_round_trips_through_the_descriptor() {
+        let id = egui::Id::new("window_width");
+        let mut memory = egui::Memory::default();
+        memory.data.insert_temp(id, 320.0_f32);
+
+        let platform = platform_with(PlatformDescriptor {
+            memory: Some(memory),
+            ..Default::default()
+        });
+        assert_eq!(platform.memory().data.get_temp::<f32>(id), Some(320.0));
+    }
+
+    #[test]
+    fn digit_keys_are_translated_but_symbols_are_text() {
+        let mut platform = platform();
+        platform.handle_events(&[
+            keyboard_input::<()>(Pressed, VirtualKeyCode::Key5),
+            received_character('5'),
+            modifiers_changed(ModifiersState::SHIFT),
+            received_character('%'),
+        ]);
+        assert_eq!(key_events(&platform), [(Key::Num5, true)]);
+        assert_eq!(text_events(&platform), ["5", "%"]);
+    }
+
+    #[test]
+    fn wheel_events_in_one_frame_accumulate() {
+        let mut platform = platform();
+        platform.handle_events(&[mouse_wheel::<()>(0.0, 1.0), mouse_wheel(0.0, 2.0)]);
+        assert_eq!(scroll_events(&platform), [vec2(0.0, 8.0), vec2(0.0, 16.0)]);
+
+        platform.begin_frame();
+        assert_eq!(
+            platform.context().input(|i| i.scroll_delta),
+            vec2(0.0, 24.0)
+        );
+        platform.end_frame(None);
+        assert!(platform.pending_events().is_empty());
+    }
+
+    #[test]
+    fn command_wheel_zooms_instead_of_scrolling() {
+        let mut platform = platform();
+        platform.handle_events(&[modifiers_changed::<()>(command()), mouse_wheel(0.0, 1.0)]);
+        assert!(scroll_events(&platform).is_empty());
+        assert!(matches!(
+            platform.pending_events(),
+            [egui::Event::Zoom(factor)] if *factor > 1.0
+        ));
+    }
+
+    #[test]
+    fn touches_are_translated_and_emulate_the_pointer() {
+        let touch = |phase, x, y| {
+            window_event::<()>(WindowEvent::Touch(winit::event::Touch {
+                device_id: device_id(),
+                phase,
+                location: PhysicalPosition::new(x, y),
+                force: None,
+                id: 7,
+            }))
+        };
+        let mut platform = platform_with(PlatformDescriptor {
+            scale_factor: 2.0,
+            ..Default::default()
+        });
+        platform.handle_events(&[
+            touch(TouchPhase::Started, 20.0, 20.0),
+            touch(TouchPhase::Moved, 40.0, 20.0),
+            touch(TouchPhase::Ended, 40.0, 20.0),
+        ]);
+
+        let touches: Vec<_> = platform
+            .pending_events()
+            .iter()
+            .filter_map(|event| match event {
+                egui::Event::Touch { id, phase, pos, .. } => Some((*id, *phase, *pos)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            touches,
+            [
+                (egui::TouchId(7), egui::TouchPhase::Start, pos2(10.0, 10.0)),
+                (egui::TouchId(7), egui::TouchPhase::Move, pos2(20.0, 10.0)),
+                (egui::TouchId(7), egui::TouchPhase::End, pos2(20.0, 10.0)),
+            ]
+        );
+
+        let buttons: Vec<_> = platform
+            .pending_events()
+            .iter()
+            .filter_map(|event| match event {
+                egui::Event::PointerButton { pressed, .. } => Some(*pressed),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(buttons, [true, false]);
+    }
+
+    #[test]
+    fn special_keys_produce_no_events() {
+        use VirtualKeyCode::*;
+        for key in [Snapshot, Pause, Scroll, Apps] {
+            let mut platform = platform();
+            platform.handle_events(&[
+                keyboard_input::<()>(Pressed, key),
+                keyboard_input(Released, key),
+            ]);
+            assert!(platform.pending_events().is_empty(), "{:?}", key);
+        }
+    }
+
+    #[test]
+    fn diagonal_pixel_deltas_keep_both_axes() {
+        let mut platform = platform();
+        platform.handle_events(&[
+            mouse_wheel_pixels::<()>(3.0, -4.0),
+            mouse_wheel_pixels(3.0, -4.0),
+            // Line deltas scroll in the same direction as pixel deltas of the same sign.
+            mouse_wheel(1.0, -1.0),
+        ]);
+        assert_eq!(
+            scroll_events(&platform),
+            [vec2(3.0, -4.0), vec2(3.0, -4.0), vec2(8.0, -8.0)]
+        );
+    }
+
+    #[test]
+    fn modifier_changes_reach_raw_input() {
+        let mut platform = platform();
+        platform.handle_event(&modifiers_changed::<()>(ModifiersState::SHIFT));
+        assert!(platform.raw_input().modifiers.shift);
+        assert!(!platform.raw_input().modifiers.ctrl);
+
+        platform.handle_event(&modifiers_changed::<()>(ModifiersState::CTRL));
+        assert!(!platform.raw_input().modifiers.shift);
+        assert!(platform.raw_input().modifiers.ctrl);
+
+        platform.handle_event(&modifiers_changed::<()>(ModifiersState::empty()));
+        assert_eq!(platform.raw_input().modifiers, egui::Modifiers::default());
+    }
+
+    #[test]
+    fn space_tab_and_enter_send_keys_without_duplicate_text() {
+        let mut platform = platform();
+        platform.handle_events(&[
+            keyboard_input::<()>(Pressed, VirtualKeyCode::Space),
+            received_character(' '),
+            keyboard_input(Pressed, VirtualKeyCode::Tab),
+            received_character('\t'),
+            keyboard_input(Pressed, VirtualKeyCode::Return),
+            received_character('\r'),
+        ]);
+        assert_eq!(
+            key_events(&platform),
+            [(Key::Space, true), (Key::Tab, true), (Key::Enter, true)]
+        );
+        // Text fields insert the space from the text, buttons react to the key.
+        assert_eq!(text_events(&platform), [" "]);
+    }
+
+    #[test]
+    fn delete_and_backspace_send_no_text() {
+        let mut platform = platform();
+        platform.handle_events(&[
+            keyboard_input::<()>(Pressed, VirtualKeyCode::Back),
+            received_character('\x08'),
+            keyboard_input(Pressed, VirtualKeyCode::Delete),
+            received_character('\x7f'),
+        ]);
+        assert_eq!(
+            key_events(&platform),
+            [(Key::Backspace, true), (Key::Delete, true)]
+        );
+        assert!(text_events(&platform).is_empty());
+    }
+
+    #[test]
+    fn enter_inserts_newlines_only_in_multiline_fields() {
+        // Shows a focused text field, presses Enter and returns the text and whether the
+        // field lost focus.
+        fn press_enter(multiline: bool) -> (String, bool) {
+            let mut text = String::new();
+            let show = |platform: &mut Platform, text: &mut String| {
+                platform.begin_frame();
+                let mut response = None;
+                egui::CentralPanel::default().show(&platform.context(), |ui| {
+                    let edit = if multiline {
+                        egui::TextEdit::multiline(text)
+                    } else {
+                        egui::TextEdit::singleline(text)
+                    };
+                    response = Some(ui.add(edit));
+                });
+                platform.end_frame(None);
+                response.unwrap()
+            };
+
+            let mut platform = platform();
+            show(&mut platform, &mut text).request_focus();
+            show(&mut platform, &mut text);
+            platform.handle_events(&[
+                keyboard_input::<()>(Pressed, VirtualKeyCode::Return),
+                received_character('\r'),
+                keyboard_input(Released, VirtualKeyCode::Return),
+            ]);
+            let lost_focus = show(&mut platform, &mut text).lost_focus();
+            (text, lost_focus)
+        }
+
+        assert_eq!(press_enter(true), ("\n".to_owned(), false));
+        assert_eq!(press_enter(false), (String::new(), true));
+    }
+
+    #[test]
+    fn tab_and_shift_tab_send_keys_without_text() {
+        let mut platform = platform();
+        platform.handle_events(&[
+            keyboard_input::<()>(Pressed, VirtualKeyCode::Tab),
+            received_character('\t'),
+            modifiers_changed(ModifiersState::SHIFT),
+            keyboard_input(Pressed, VirtualKeyCode::Tab),
+            received_character('\t'),
+        ]);
+        let tabs: Vec<_> = platform
+            .pending_events()
+            .iter()
+            .filter_map(|event| match event {
+                egui::Event::Key {
+                    key: Key::Tab,
+                    modifiers,
+                    ..
+                } => Some(modifiers.shift),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(tabs, [false, true]);
+        assert!(text_events(&platform).is_empty());
+    }
+
+    #[test]
+    fn touchpad_magnify_zooms_proportionally() {
+        let magnify = |delta| {
+            window_event::<()>(WindowEvent::TouchpadMagnify {
+                device_id: device_id(),
+                delta,
+                phase: TouchPhase::Moved,
+            })
+        };
+        let mut platform = platform();
+        platform.handle_events(&[magnify(0.5), magnify(-0.5)]);
+        let zooms: Vec<_> = platform
+            .pending_events()
+            .iter()
+            .filter_map(|event| match event {
+                egui::Event::Zoom(factor) => Some(*factor),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(zooms, [0.5_f32.exp(), (-0.5_f32).exp()]);
+        // Pinching out and back in by the same amount ends at the original zoom.
+        assert!((zooms[0] * zooms[1] - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn rotate_handler_receives_rotation_deltas() {
+        use std::{cell::RefCell, rc::Rc};
+
+        let deltas = Rc::new(RefCell::new(Vec::new()));
+        let mut platform = platform();
+        platform.set_rotate_handler({
+            let deltas = deltas.clone();
+            move |delta| deltas.borrow_mut().push(delta)
+        });
+        platform.handle_events(&[
+            window_event::<()>(WindowEvent::TouchpadRotate {
+                device_id: device_id(),
+                delta: 15.0,
+                phase: TouchPhase::Moved,
+            }),
+            window_event(WindowEvent::TouchpadRotate {
+                device_id: device_id(),
+                delta: -5.0,
+                phase: TouchPhase::Moved,
+            }),
+        ]);
+        assert_eq!(*deltas.borrow(), [15.0, -5.0]);
+        assert!(platform.pending_events().is_empty());
+    }
+
+    #[test]
+    fn smart_magnify_calls_the_handler() {
+        use std::{cell::Cell, rc::Rc};
+
+        let calls = Rc::new(Cell::new(0));
+        let mut platform = platform();
+        platform.set_smart_magnify_handler({
+            let calls = calls.clone();
+            move || calls.set(calls.get() + 1)
+        });
+        platform.handle_event(&window_event::<()>(WindowEvent::SmartMagnify {
+            device_id: device_id(),
+        }));
+        assert_eq!(calls.get(), 1);
+        assert!(platform.pending_events().is_empty());
+    }
+
+    // Needs a desktop session and replaces the clipboard content, so it only runs on request.
+    #[cfg(feature = "clipboard")]
+    #[test]
+    #[ignore]
+    fn set_clipboard_text_can_be_pasted() {
+        let mut platform = Platform::new(PlatformDescriptor::default());
+        platform
+            .set_clipboard_text("egui_winit_platform paste test".to_owned())
+            .unwrap();
+        platform.paste();
+        assert_eq!(text_events(&platform), ["egui_winit_platform paste test"]);
+    }
+}
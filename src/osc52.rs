@@ -0,0 +1,69 @@
+//! Copying to the controlling terminal for the `osc52` feature.
+
+use std::io::Write;
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Asks the terminal to put the given text into the clipboard with an OSC 52 escape sequence.
+/// Terminals that don't support it ignore the sequence.
+pub(crate) fn copy(text: &str) -> std::io::Result<()> {
+    let mut stdout = std::io::stdout().lock();
+    stdout.write_all(sequence(text).as_bytes())?;
+    stdout.flush()
+}
+
+/// Builds the escape sequence that sets the clipboard selection to `text`.
+fn sequence(text: &str) -> String {
+    format!("\x1b]52;c;{}\x07", base64(text.as_bytes()))
+}
+
+/// Encodes the bytes as standard base64 with padding.
+fn base64(bytes: &[u8]) -> String {
+    // `usize::div_ceil` needs a newer Rust than egui does.
+    #[allow(clippy::manual_div_ceil)]
+    let mut encoded = String::with_capacity((bytes.len() + 2) / 3 * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let indices = [
+            b[0] >> 2,
+            (b[0] & 0b11) << 4 | b[1] >> 4,
+            (b[1] & 0b1111) << 2 | b[2] >> 6,
+            b[2] & 0b111111,
+        ];
+        // A chunk of n bytes is encoded in n + 1 characters, the rest is padding.
+        for (i, index) in indices.iter().enumerate() {
+            if i <= chunk.len() {
+                encoded.push(BASE64_ALPHABET[*index as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base64_pads_partial_chunks() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64(b"foob"), "Zm9vYg==");
+        assert_eq!(base64("ä€".as_bytes()), "w6Tigqw=");
+    }
+
+    #[test]
+    fn sequence_is_framed_as_osc_52() {
+        assert_eq!(sequence("foo"), "\x1b]52;c;Zm9v\x07");
+        assert_eq!(sequence(""), "\x1b]52;c;\x07");
+    }
+}
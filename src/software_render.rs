@@ -0,0 +1,254 @@
+//! A minimal CPU rasterizer for the `software-render` feature.
+
+use std::collections::HashMap;
+
+use egui::{
+    epaint::{ImageDelta, Primitive, Vertex},
+    ClippedPrimitive, Color32, ColorImage, ImageData, TextureId, TexturesDelta,
+};
+
+/// Draws tessellated egui output into an in-memory image without a GPU, e.g. for snapshot tests
+/// of simple UIs. Triangles are filled at pixel centers without anti-aliasing beyond egui's own
+/// feathering, textures are sampled with the nearest texel and paint callbacks are skipped.
+///
+/// ```
+/// use egui_winit_platform::SoftwareRenderer;
+///
+/// let ctx = egui::Context::default();
+/// let input = egui::RawInput {
+///     screen_rect: Some(egui::Rect::from_min_size(egui::Pos2::ZERO, egui::vec2(32.0, 32.0))),
+///     pixels_per_point: Some(1.0),
+///     ..Default::default()
+/// };
+/// let output = ctx.run(input, |ctx| {
+///     let rect = egui::Rect::from_min_size(egui::pos2(8.0, 8.0), egui::vec2(16.0, 16.0));
+///     ctx.layer_painter(egui::LayerId::background())
+///         .rect_filled(rect, 0.0, egui::Color32::RED);
+/// });
+/// let primitives = ctx.tessellate(output.shapes);
+///
+/// let mut renderer = SoftwareRenderer::new();
+/// let image = renderer.render(&primitives, &output.textures_delta, 1.0, [32, 32]);
+/// assert_eq!(image.pixels[16 * 32 + 16], egui::Color32::RED);
+/// assert_eq!(image.pixels[0], egui::Color32::TRANSPARENT);
+/// ```
+#[derive(Default)]
+pub struct SoftwareRenderer {
+    textures: HashMap<TextureId, ColorImage>,
+}
+
+impl SoftwareRenderer {
+    /// Creates a renderer without any textures. The font texture is uploaded with the
+    /// `textures_delta` of the first frame.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Applies the texture changes and draws the primitives into a transparent image of the
+    /// given size in physical pixels. Pixels are premultiplied sRGBA, like egui's colors.
+    pub fn render(
+        &mut self,
+        primitives: &[ClippedPrimitive],
+        textures_delta: &TexturesDelta,
+        pixels_per_point: f32,
+        size: [usize; 2],
+    ) -> ColorImage {
+        for (id, delta) in &textures_delta.set {
+            self.set_texture(*id, delta);
+        }
+
+        let mut image = ColorImage::new(size, Color32::TRANSPARENT);
+        for ClippedPrimitive {
+            clip_rect,
+            primitive,
+        } in primitives
+        {
+            let mesh = match primitive {
+                Primitive::Mesh(mesh) => mesh,
+                Primitive::Callback(_) => continue,
+            };
+            let texture = match self.textures.get(&mesh.texture_id) {
+                Some(texture) => texture,
+                None => continue,
+            };
+            let clip = [
+                (clip_rect.min.x * pixels_per_point).round().max(0.0) as usize,
+                (clip_rect.min.y * pixels_per_point).round().max(0.0) as usize,
+                ((clip_rect.max.x * pixels_per_point).round().max(0.0) as usize).min(size[0]),
+                ((clip_rect.max.y * pixels_per_point).round().max(0.0) as usize).min(size[1]),
+            ];
+            for triangle in mesh.indices.chunks_exact(3) {
+                let vertices = [
+                    &mesh.vertices[triangle[0] as usize],
+                    &mesh.vertices[triangle[1] as usize],
+                    &mesh.vertices[triangle[2] as usize],
+                ];
+                draw_triangle(&mut image, texture, clip, vertices, pixels_per_point);
+            }
+        }
+
+        for id in &textures_delta.free {
+            self.textures.remove(id);
+        }
+        image
+    }
+
+    fn set_texture(&mut self, id: TextureId, delta: &ImageDelta) {
+        let patch = match &delta.image {
+            ImageData::Color(image) => image.clone(),
+            ImageData::Font(image) => ColorImage {
+                size: image.size,
+                pixels: image.srgba_pixels(None).collect(),
+            },
+        };
+        match (delta.pos, self.textures.get_mut(&id)) {
+            (Some([x, y]), Some(texture)) => {
+                for row in 0..patch.size[1] {
+                    let src = row * patch.size[0];
+                    let dst = (y + row) * texture.size[0] + x;
+                    texture.pixels[dst..dst + patch.size[0]]
+                        .copy_from_slice(&patch.pixels[src..src + patch.size[0]]);
+                }
+            }
+            _ => {
+                self.textures.insert(id, patch);
+            }
+        }
+    }
+}
+
+/// Fills the pixels whose centers lie inside the triangle, clipped to `[min_x, min_y, max_x, max_y]`.
+/// Centers exactly on an edge follow the top-left rule, so edges shared by two triangles are only
+/// drawn once.
+fn draw_triangle(
+    image: &mut ColorImage,
+    texture: &ColorImage,
+    [min_x, min_y, max_x, max_y]: [usize; 4],
+    mut vertices: [&Vertex; 3],
+    pixels_per_point: f32,
+) {
+    let mut pos = vertices.map(|v| (v.pos.x * pixels_per_point, v.pos.y * pixels_per_point));
+    let mut area = edge(pos[0], pos[1], pos[2]);
+    if area == 0.0 {
+        return;
+    }
+    // Use one winding for all triangles, so the edge orientation tells top and left edges apart.
+    if area < 0.0 {
+        vertices.swap(1, 2);
+        pos.swap(1, 2);
+        area = -area;
+    }
+    let edges = [(pos[1], pos[2]), (pos[2], pos[0]), (pos[0], pos[1])];
+
+    let bounds_min_x = pos.iter().map(|p| p.0).fold(f32::INFINITY, f32::min);
+    let bounds_min_y = pos.iter().map(|p| p.1).fold(f32::INFINITY, f32::min);
+    let bounds_max_x = pos.iter().map(|p| p.0).fold(f32::NEG_INFINITY, f32::max);
+    let bounds_max_y = pos.iter().map(|p| p.1).fold(f32::NEG_INFINITY, f32::max);
+    let x_range = (bounds_min_x.floor().max(0.0) as usize).max(min_x)
+        ..(bounds_max_x.ceil().max(0.0) as usize).min(max_x);
+    let y_range = (bounds_min_y.floor().max(0.0) as usize).max(min_y)
+        ..(bounds_max_y.ceil().max(0.0) as usize).min(max_y);
+
+    for y in y_range {
+        for x in x_range.clone() {
+            let p = (x as f32 + 0.5, y as f32 + 0.5);
+            // Barycentric weights, positive inside the triangle.
+            let w = edges.map(|(a, b)| edge(a, b, p) / area);
+            let inside = w
+                .iter()
+                .zip(&edges)
+                .all(|(w, (a, b))| *w > 0.0 || (*w == 0.0 && is_top_left(*a, *b)));
+            if !inside {
+                continue;
+            }
+
+            let u = w[0] * vertices[0].uv.x + w[1] * vertices[1].uv.x + w[2] * vertices[2].uv.x;
+            let v = w[0] * vertices[0].uv.y + w[1] * vertices[1].uv.y + w[2] * vertices[2].uv.y;
+            let texel = sample(texture, u, v).to_array();
+            let mut src = [0; 4];
+            for (i, channel) in src.iter_mut().enumerate() {
+                let color = w[0] * vertices[0].color[i] as f32
+                    + w[1] * vertices[1].color[i] as f32
+                    + w[2] * vertices[2].color[i] as f32;
+                *channel = (color * texel[i] as f32 / 255.0).round() as u8;
+            }
+
+            // Premultiplied "over" blending, like egui's GPU backends.
+            let pixel = &mut image.pixels[y * image.size[0] + x];
+            let dst = pixel.to_array();
+            let mut out = [0; 4];
+            for i in 0..4 {
+                out[i] = src[i]
+                    .saturating_add((dst[i] as f32 * (255 - src[3]) as f32 / 255.0).round() as u8);
+            }
+            *pixel = Color32::from_rgba_premultiplied(out[0], out[1], out[2], out[3]);
+        }
+    }
+}
+
+/// Returns twice the signed area of the triangle `a`, `b`, `c`.
+fn edge(a: (f32, f32), b: (f32, f32), c: (f32, f32)) -> f32 {
+    (b.0 - a.0) * (c.1 - a.1) - (b.1 - a.1) * (c.0 - a.0)
+}
+
+/// Returns `true` if the edge from `a` to `b` is a top or left edge of a triangle with positive
+/// area. In screen coordinates with y pointing down, those run rightwards or upwards.
+fn is_top_left(a: (f32, f32), b: (f32, f32)) -> bool {
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    (dy == 0.0 && dx > 0.0) || dy < 0.0
+}
+
+/// Samples the texel nearest to the given texture coordinates.
+fn sample(texture: &ColorImage, u: f32, v: f32) -> Color32 {
+    let [width, height] = texture.size;
+    if width == 0 || height == 0 {
+        return Color32::TRANSPARENT;
+    }
+    let x = ((u * width as f32) as usize).min(width - 1);
+    let y = ((v * height as f32) as usize).min(height - 1);
+    texture.pixels[y * width + x]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render_rect(color: Color32) -> ColorImage {
+        let ctx = egui::Context::default();
+        let input = egui::RawInput {
+            screen_rect: Some(egui::Rect::from_min_size(
+                egui::Pos2::ZERO,
+                egui::vec2(32.0, 32.0),
+            )),
+            pixels_per_point: Some(1.0),
+            ..Default::default()
+        };
+        let output = ctx.run(input, |ctx| {
+            let rect = egui::Rect::from_min_size(egui::pos2(8.0, 8.0), egui::vec2(16.0, 16.0));
+            ctx.layer_painter(egui::LayerId::background())
+                .rect_filled(rect, 0.0, color);
+        });
+        let primitives = ctx.tessellate(output.shapes);
+        SoftwareRenderer::new().render(&primitives, &output.textures_delta, 1.0, [32, 32])
+    }
+
+    #[test]
+    fn translucent_fill_is_even_along_shared_edges() {
+        let color = Color32::from_rgba_premultiplied(0, 0, 128, 128);
+        let image = render_rect(color);
+        let pixel = |x: usize, y: usize| image.pixels[y * 32 + x];
+
+        assert_eq!(pixel(18, 12), color);
+        for i in 9..23 {
+            assert_eq!(pixel(i, i), color, "diagonal pixel {}", i);
+            assert_eq!(pixel(i, 31 - i), color, "anti-diagonal pixel {}", i);
+        }
+    }
+
+    #[test]
+    fn outside_stays_transparent() {
+        let image = render_rect(Color32::RED);
+        assert_eq!(image.pixels[0], Color32::TRANSPARENT);
+        assert_eq!(image.pixels[31 * 32 + 31], Color32::TRANSPARENT);
+    }
+}
@@ -0,0 +1,118 @@
+//! Builders for synthetic winit events, to test input handling without a window or event loop.
+//!
+//! All events use the same dummy window and device ids. Don't pass them to winit itself.
+//!
+//! ```
+//! use egui_winit_platform::{test_utils, Platform, PlatformDescriptor};
+//! use winit::event::{ElementState, MouseButton};
+//!
+//! let mut platform = Platform::new_without_clipboard(PlatformDescriptor::default());
+//! platform.handle_events(&[
+//!     test_utils::cursor_moved::<()>(10.0, 20.0),
+//!     test_utils::mouse_input(ElementState::Pressed, MouseButton::Left),
+//! ]);
+//! assert!(matches!(
+//!     platform.pending_events(),
+//!     [
+//!         egui::Event::PointerMoved(_),
+//!         egui::Event::PointerButton { pressed: true, .. }
+//!     ]
+//! ));
+//! ```
+
+use winit::{
+    dpi::{PhysicalPosition, PhysicalSize},
+    event::{
+        DeviceId, ElementState, Event, KeyboardInput, ModifiersState, MouseButton,
+        MouseScrollDelta, TouchPhase, VirtualKeyCode, WindowEvent,
+    },
+    window::WindowId,
+};
+
+/// Wraps the given window event into an event of the dummy window.
+pub fn window_event<T>(event: WindowEvent<'static>) -> Event<'static, T> {
+    Event::WindowEvent {
+        // SAFETY: The dummy id is only compared, it is never passed to winit.
+        window_id: unsafe { WindowId::dummy() },
+        event,
+    }
+}
+
+/// Returns the dummy device id used by all events, for building other window events.
+pub fn device_id() -> DeviceId {
+    // SAFETY: The dummy id is only compared and hashed, it is never passed to winit.
+    unsafe { DeviceId::dummy() }
+}
+
+/// Creates a resize of the window to the given physical size.
+pub fn resized<T>(width: u32, height: u32) -> Event<'static, T> {
+    window_event(WindowEvent::Resized(PhysicalSize::new(width, height)))
+}
+
+/// Creates a cursor move to the given physical position.
+#[allow(deprecated)]
+pub fn cursor_moved<T>(x: f64, y: f64) -> Event<'static, T> {
+    window_event(WindowEvent::CursorMoved {
+        device_id: device_id(),
+        position: PhysicalPosition::new(x, y),
+        modifiers: ModifiersState::empty(),
+    })
+}
+
+/// Creates a press or release of the given mouse button.
+#[allow(deprecated)]
+pub fn mouse_input<T>(state: ElementState, button: MouseButton) -> Event<'static, T> {
+    window_event(WindowEvent::MouseInput {
+        device_id: device_id(),
+        state,
+        button,
+        modifiers: ModifiersState::empty(),
+    })
+}
+
+/// Creates a mouse wheel movement by the given number of lines.
+#[allow(deprecated)]
+pub fn mouse_wheel<T>(x: f32, y: f32) -> Event<'static, T> {
+    window_event(WindowEvent::MouseWheel {
+        device_id: device_id(),
+        delta: MouseScrollDelta::LineDelta(x, y),
+        phase: TouchPhase::Moved,
+        modifiers: ModifiersState::empty(),
+    })
+}
+
+/// Creates a touchpad scroll by the given physical pixels.
+#[allow(deprecated)]
+pub fn mouse_wheel_pixels<T>(x: f64, y: f64) -> Event<'static, T> {
+    window_event(WindowEvent::MouseWheel {
+        device_id: device_id(),
+        delta: MouseScrollDelta::PixelDelta(PhysicalPosition::new(x, y)),
+        phase: TouchPhase::Moved,
+        modifiers: ModifiersState::empty(),
+    })
+}
+
+/// Creates a change of the held modifiers. Send it before key or mouse events to test shortcuts.
+pub fn modifiers_changed<T>(modifiers: ModifiersState) -> Event<'static, T> {
+    window_event(WindowEvent::ModifiersChanged(modifiers))
+}
+
+/// Creates a press or release of the given key.
+#[allow(deprecated)]
+pub fn keyboard_input<T>(state: ElementState, key: VirtualKeyCode) -> Event<'static, T> {
+    window_event(WindowEvent::KeyboardInput {
+        device_id: device_id(),
+        input: KeyboardInput {
+            scancode: 0,
+            state,
+            virtual_keycode: Some(key),
+            modifiers: ModifiersState::empty(),
+        },
+        is_synthetic: false,
+    })
+}
+
+/// Creates a typed character.
+pub fn received_character<T>(ch: char) -> Event<'static, T> {
+    window_event(WindowEvent::ReceivedCharacter(ch))
+}
@@ -0,0 +1,41 @@
+//! Runs a frame with only the required dependencies. Run it with
+//! `cargo test --no-default-features` to check that event translation doesn't depend on any
+//! optional feature.
+
+use egui_winit_platform::{Platform, PlatformDescriptor};
+use winit::{
+    dpi::PhysicalSize,
+    event::{Event, WindowEvent},
+    window::WindowId,
+};
+
+fn window_event(event: WindowEvent<'static>) -> Event<'static, ()> {
+    Event::WindowEvent {
+        window_id: WindowId::from(0),
+        event,
+    }
+}
+
+#[test]
+fn frame_without_optional_features() {
+    let mut platform = Platform::new_without_clipboard(PlatformDescriptor::default());
+    platform.handle_events(&[
+        window_event(WindowEvent::Resized(PhysicalSize::new(400, 300))),
+        window_event(WindowEvent::ReceivedCharacter('a')),
+    ]);
+
+    platform.begin_frame();
+    let ctx = platform.context();
+    ctx.input(|i| {
+        assert_eq!(i.screen_rect().size(), egui::vec2(400.0, 300.0));
+        assert_eq!(i.events, [egui::Event::Text("a".to_owned())]);
+    });
+    // Text needs fonts, which are optional as well.
+    let rect = egui::Rect::from_min_size(egui::pos2(10.0, 10.0), egui::vec2(20.0, 20.0));
+    ctx.layer_painter(egui::LayerId::background())
+        .rect_filled(rect, 0.0, egui::Color32::RED);
+    let output = platform.end_frame(None);
+
+    assert!(!ctx.tessellate(output.shapes).is_empty());
+    assert!(platform.pending_events().is_empty());
+}